@@ -9,12 +9,719 @@ export const enum Modifiers {
   Shift = 4,
   Win = 8
 }
-export declare function registerHotkey(modifier: Modifiers, vk: number, callback: (...args: any[]) => any): void
-export declare function openWebview(title: string, width: number, height: number): WebviewHandle
-export declare function registerAltRelease(callback: (...args: any[]) => any): void
+/** Payload passed to a hotkey callback, decoded from the WM_HOTKEY message */
+export interface HotkeyEvent {
+  vk: number
+  modifiers: number
+  hotkeyId: number
+}
+/**
+ * Retries for a hotkey whose combo another app still holds, e.g. one that is
+ * shutting down as this one starts. Other registration errors fail at once.
+ */
+export interface RetryPolicy {
+  /** Extra registration attempts after the first (default 0: no retry) */
+  attempts?: number
+  /** Wait between attempts in milliseconds (default 250) */
+  delayMs?: number
+}
+/** Options for `register_hotkey_ex`; omitted fields take the defaults below */
+export interface HotkeyOptions {
+  modifiers: number | Array<Modifiers>
+  vk: number
+  /**
+   * Fixed hotkey ID, 0 to 0xBFFF, e.g. to keep it stable across restarts;
+   * must not be in use by another live hotkey (default: a fresh ID)
+   */
+  id?: number
+  /** Fire once while the combo is held (default true; needs Windows 7+) */
+  noRepeat?: boolean
+  /** Drop fires within this many ms of the previous one (default 0, off) */
+  debounceMs?: number
+  /** Keep trying while another app still holds the combo (default: no retry) */
+  retry?: RetryPolicy
+  /** Consecutive failed callback calls before giving up (default 3) */
+  maxCallFailures?: number
+  /** Only fire while this window is in the foreground (default: anywhere) */
+  scopeHwnd?: number
+  /**
+   * Called every `heartbeatMs` from the listener thread with the number of
+   * messages its loop has handled, to confirm it is alive (default: off)
+   */
+  onHeartbeat?: (...args: any[]) => any
+  /** Heartbeat interval in milliseconds (default 60000, at least 1000) */
+  heartbeatMs?: number
+  /**
+   * Re-check the registration this often, re-registering it if the OS
+   * dropped it (default: off, at least 250)
+   */
+  verifyMs?: number
+  /** Called with the hotkey ID when a check finds the registration gone */
+  onLost?: (...args: any[]) => any
+  /** Called with the hotkey ID once a lost registration is back */
+  onRestored?: (...args: any[]) => any
+}
+/**
+ * Registers a global hotkey. `noRepeat` adds MOD_NOREPEAT so holding the
+ * combo fires the callback only once. `debounceMs` drops fires that follow
+ * the previous one too closely, e.g. an accidental double-tap. After
+ * `maxCallFailures` consecutive failed callback calls, e.g. once the JS
+ * environment is gone, the hotkey is unregistered for good.
+ * `scopeHwnd` limits the hotkey to one window, but only best-effort: the
+ * OS still registers it globally, so the combo is swallowed everywhere and
+ * merely ignored while another window is in the foreground.
+ * Some apps and OS events silently drop registrations; set `verifyMs` to
+ * have them re-checked and restored, reported via `onLost`/`onRestored`.
+ * If registration fails the callback receives the error: `code` is
+ * `InvalidArg` when another app already owns the combo (Win32 error 1409).
+ */
+export declare function registerHotkeyEx(options: HotkeyOptions, callback: (...args: any[]) => any): HotkeyHandle
+/**
+ * Registers a global hotkey with the defaults of `register_hotkey_ex`, which
+ * takes every other option (`noRepeat`, `debounceMs`, `retry`, ...).
+ */
+export declare function registerHotkey(modifiers: number | Array<Modifiers>, vk: number, callback: (...args: any[]) => any): HotkeyHandle
+/** One entry for `register_hotkeys` */
+export interface HotkeyBinding {
+  modifiers: number | Array<Modifiers>
+  vk: number
+  callback: (...args: any[]) => any
+  noRepeat?: boolean
+  debounceMs?: number
+  scopeHwnd?: number
+}
+/**
+ * Registers several hotkeys served by a single listener thread. The batch is
+ * all-or-nothing: if any combo fails to register, none stay registered and
+ * that entry's callback receives the error. `retry` applies to each combo;
+ * `maxCallFailures` counts failed calls across all of them.
+ */
+export declare function registerHotkeys(bindings: Array<HotkeyBinding>, retry?: RetryPolicy | undefined | null, maxCallFailures?: number | undefined | null): HotkeyHandle
+/**
+ * Checks whether Windows would accept this combo right now by registering it
+ * under a throwaway ID and immediately unregistering it. Hotkeys already
+ * registered through `register_hotkey` are never touched.
+ */
+export declare function isHotkeyAvailable(modifiers: number | Array<Modifiers>, vk: number): boolean
+/**
+ * Pauses (`true`) or resumes (`false`) every hotkey callback, e.g. while a
+ * game runs. The combos stay registered, so no other app can take them in
+ * the meantime; presses while suspended are swallowed and never queued.
+ */
+export declare function suspendHotkeys(suspended: boolean): void
+/**
+ * Stops the listener serving hotkey `id` without needing its `HotkeyHandle`,
+ * e.g. when only the ID was kept. A batch listener stops as a whole. Returns
+ * false if no live listener serves that ID.
+ */
+export declare function cancelHotkey(id: number): boolean
+/** How `open_webview` interprets its `content` string */
+export const enum ContentKind {
+  Html = 0,
+  Url = 1
+}
+/** Window options for `open_webview`; omitted fields keep the overlay defaults */
+export interface WebviewOptions {
+  resizable?: boolean
+  frameless?: boolean
+  debug?: boolean
+  visible?: boolean
+  /**
+   * Initial screen position of the top-left corner; give both or neither
+   * (default: the OS picks, centered)
+   */
+  x?: number
+  y?: number
+  /**
+   * Exact initial window rect, e.g. saved from `get_geometry`; replaces
+   * `x`/`y` and the `width`/`height` arguments
+   */
+  geometry?: WindowRect
+  /**
+   * Make the process per-monitor DPI aware before the first window opens, so
+   * the overlay is sharp on high-DPI displays (default true). Pass false if
+   * the host app manages DPI awareness itself.
+   */
+  dpiAware?: boolean
+  /**
+   * Make it a tool window, kept out of the taskbar and Alt-Tab like a HUD
+   * should be (default false)
+   */
+  toolWindow?: boolean
+  /** Receives a `WebviewEvent` as the window goes through its lifecycle */
+  onEvent?: (...args: any[]) => any
+}
+/** Lifecycle notification passed to `WebviewOptions.onEvent` */
+export interface WebviewEvent {
+  /**
+   * `"ready"` once the window is up and the handle usable, `"closed"` when
+   * it has gone away, or `"error"` if it failed to build or run
+   */
+  kind: string
+  /** What went wrong, for `"error"` */
+  message?: string
+}
+/**
+ * Opens a webview window. `content` is inline HTML by default, or a URL
+ * (remote or `file://`) when `contentKind` is `ContentKind.Url`.
+ *
+ * `onMessage` receives every string the page passes to
+ * `window.external.invoke(...)`. The invoke fires on the webview thread; the
+ * callback is queued onto the Node event loop, so it runs asynchronously and
+ * can't return a value to the page — reply with `WebviewHandle.eval` instead.
+ *
+ * `options.onEvent` observes the window's lifecycle: `ready`, then `closed`
+ * when it goes away, with an `error` first if building or running failed.
+ *
+ * The window is built on its own thread, but the handle is usable at once:
+ * calls made before it's `ready` are queued and run in order once it is.
+ */
+export declare function openWebview(title: string, width: number, height: number, content?: string | undefined | null, contentKind?: ContentKind | undefined | null, onMessage?: (...args: any[]) => any | undefined | null, options?: WebviewOptions | undefined | null): WebviewHandle
+/**
+ * Opens a webview showing a static web build: `dir` is served over HTTP on
+ * a random loopback port and the window loads `entry` (e.g. `index.html`)
+ * from it, so the page can use `fetch` and module scripts that `file://`
+ * URLs block. The server stops when the window closes. Other arguments are
+ * as for `open_webview`.
+ */
+export declare function openWebviewFromDir(dir: string, entry: string, title: string, width: number, height: number, onMessage?: (...args: any[]) => any | undefined | null, options?: WebviewOptions | undefined | null): WebviewHandle
+/**
+ * Closes every window opened by `open_webview`, including ones whose
+ * handle JS has dropped. Their `onEvent` callbacks still see `closed`.
+ */
+export declare function closeAllWebviews(): void
+/** Which key transitions `register_key_release` reports */
+export const enum KeyEventKind {
+  Up = 0,
+  Down = 1,
+  Both = 2
+}
+/** Payload passed to a key hook callback, copied from KBDLLHOOKSTRUCT */
+export interface KeyEvent {
+  vkCode: number
+  scanCode: number
+  time: number
+  /** Whether every modifier in `KeyHookOptions.modifiers` was held (true if none) */
+  chordHeld: boolean
+  /** Whether this was a press rather than a release */
+  isDown: boolean
+}
+/** Options for `register_key_release` */
+export interface KeyHookOptions {
+  /** Keep firing on every release until `unregister_key_release` (default false: one-shot) */
+  repeat?: boolean
+  /**
+   * Swallow the watched key's release so no app sees it, e.g. so releasing
+   * Alt doesn't open the foreground app's menu (default false). This is
+   * global: apps that saw the key go down never see it come up, so keep
+   * the hook's lifetime short.
+   */
+  suppress?: boolean
+  /**
+   * Modifiers that must be held with the key, e.g. `[Modifiers.Alt]` to
+   * catch Tab only while Alt is down. A one-shot hook ignores releases
+   * without them and `suppress` lets those through; in repeat mode every
+   * release fires and `chordHeld` tells them apart.
+   */
+  modifiers?: number | Array<Modifiers>
+  /**
+   * Report presses, releases or both (default `KeyEventKind.Up`); `repeat`,
+   * `suppress` and `modifiers` then apply to every reported event
+   */
+  kind?: KeyEventKind
+}
+/**
+ * Calls `callback` the next time key `vk` is released, then unhooks; with
+ * `repeat` it fires on every release until `unregister_key_release`.
+ * `kind` switches to presses, or both, e.g. to step through a list with Tab
+ * while Alt is held.
+ * Generic modifiers (`VK_MENU`, `VK_CONTROL`, `VK_SHIFT`) match either side.
+ * Each call installs its own hook, so several can watch at once; the
+ * returned ID removes just this one via `unregister_key_release`.
+ * If the hook can't be installed, the callback receives the error instead.
+ */
+export declare function registerKeyRelease(vk: number, callback: (...args: any[]) => any, options?: KeyHookOptions | undefined | null): number
+/**
+ * Resolves with the `KeyEvent` the next time key `vk` is released, e.g.
+ * the Alt release that commits an Alt+Tab style switch, then unhooks.
+ * `modifiers` and `suppress` work as for `register_key_release`; `repeat`
+ * and `kind` are ignored. Rejects with `Cancelled` if no release comes
+ * within `timeoutMs`, which removes just this hook, or if
+ * `unregister_key_release()` (or `shutdown`) removes it first; rejects
+ * with the error if the hook can't be installed.
+ */
+export declare function waitForKeyRelease(vk: number, options?: KeyHookOptions | undefined | null, timeoutMs?: number | undefined | null): Promise<KeyEvent>
+/**
+ * Removes the key hook `id` returned by `register_key_release` (or
+ * `register_alt_release`), or every key hook when `id` is omitted. A no-op
+ * for hooks that are already gone.
+ */
+export declare function unregisterKeyRelease(id?: number | undefined | null): void
+/** Shorthand for `register_key_release` on the Alt key; left or right Alt fires it. */
+export declare function registerAltRelease(callback: (...args: any[]) => any, options?: KeyHookOptions | undefined | null): number
+/**
+ * Registers a hotkey whose press calls `onPress` (with a `HotkeyEvent`) and
+ * whose release of `vk` then calls `onRelease` (with a `KeyEvent`), e.g.
+ * hold to preview, release to dismiss. The release counts however the
+ * modifiers were let go. `HotkeyHandle.unregister` and
+ * `unregister_key_release()` each remove both halves.
+ */
+export declare function registerHoldHotkey(modifiers: number | Array<Modifiers>, vk: number, onPress: (...args: any[]) => any, onRelease: (...args: any[]) => any): HotkeyHandle
+/**
+ * Calls `callback` (with the second press's `KeyEvent`) whenever key `vk`
+ * is pressed twice within `window_ms`, e.g. double-tap Shift: gestures
+ * `register_hotkey` can't express. Pressing any other key in between, or
+ * holding `vk` down, doesn't count; generic modifiers match either side.
+ * Respects `suspend_hotkeys`. Returns a hook ID for `unregister_key_release`.
+ */
+export declare function registerDoubleTap(vk: number, windowMs: number, callback: (...args: any[]) => any): number
+/** A combo recorded by `start_hotkey_capture`, ready for `register_hotkey` */
+export interface HotkeyCapture {
+  modifiers: number
+  vk: number
+}
+/**
+ * Records the next combo the user presses, for a "press the keys you want"
+ * settings field: on the first non-modifier key press, `callback` receives
+ * `{ modifiers, vk }` and the hook is removed. That press is swallowed.
+ * If the hook can't be installed, the callback receives the error instead.
+ */
+export declare function startHotkeyCapture(callback: (...args: any[]) => any): void
+/**
+ * Stops every running `start_hotkey_capture` without reporting a combo. A
+ * no-op if no capture is running.
+ */
+export declare function cancelHotkeyCapture(): void
+/**
+ * Whether key `vk` is held down right now. A cheap point-in-time check, e.g.
+ * to tell a held modifier from a tap after a hotkey fires.
+ */
+export declare function isKeyDown(vk: number): boolean
+/** A top-level window as seen by the switcher */
+export interface WindowInfo {
+  hwnd: number
+  title: string
+  processId: number
+  /**
+   * Executable file name, e.g. `chrome.exe`; empty if the process can't be
+   * queried (elevated or protected processes)
+   */
+  processName: string
+  isMinimized: boolean
+  isMaximized: boolean
+}
+/**
+ * Narrows what `list_windows` returns; checked during enumeration, so
+ * filtered-out windows cost no process lookups and never reach JS
+ */
+export interface ListWindowsOptions {
+  /** Leave out windows of these processes, e.g. this app's own */
+  excludeProcessIds?: Array<number>
+  /** Keep minimized windows (default true) */
+  includeMinimized?: boolean
+  /** Keep only windows whose title contains this text, ignoring case */
+  titlePattern?: string
+  /**
+   * Keep EnumWindows' top-to-bottom z-order (default true); false sorts by
+   * `hwnd` instead, for a list that stays put as focus moves
+   */
+  sortByZOrder?: boolean
+}
+/**
+ * Lists visible, titled top-level windows (tool windows excluded), in
+ * z-order from top to bottom, as `EnumWindows` reports them, unless
+ * `sortByZOrder` is false. Windows DWM
+ * keeps cloaked, such as suspended UWP apps, are left out, as are windows
+ * on other virtual desktops with `currentDesktopOnly`, like Alt-Tab does;
+ * where the virtual desktop API is unavailable every desktop is listed.
+ * `options` filters further.
+ */
+export declare function listWindows(currentDesktopOnly?: boolean | undefined | null, options?: ListWindowsOptions | undefined | null): Array<WindowInfo>
+/** What `focus_window` achieved */
+export const enum FocusStatus {
+  Focused = 0,
+  /**
+   * The window runs at a higher integrity level than this process (e.g. an
+   * elevated app), so Windows won't let us activate it
+   */
+  Elevated = 1,
+  /**
+   * Another app holds the foreground lock: the user gave it input more
+   * recently than the system's foreground lock timeout. `flash_window` is
+   * the usual fallback.
+   */
+  ForegroundLocked = 2,
+  /** SetForegroundWindow refused for some other reason */
+  Refused = 3
+}
+/** Returned by `focus_window` */
+export interface FocusResult {
+  status: FocusStatus
+  /**
+   * GetLastError after SetForegroundWindow refused, for diagnostics; 0
+   * when focused or when Windows gave no code, as it often doesn't
+   */
+  code: number
+}
+/** Options for `focus_window` and `focus_window_sync` */
+export interface FocusOptions {
+  /**
+   * Inject a zero-distance mouse move first, so Windows sees this process
+   * as having just received input and lets it take the foreground more
+   * often (default false). The move is real input as far as the system is
+   * concerned: it resets idle timers and reaches mouse hooks, including
+   * `register_mouse_hook`, so it's opt-in. It doesn't help against
+   * elevated windows, and a helper process can instead be granted the
+   * right outright with `AllowSetForegroundWindow` on its side.
+   */
+  nudgeInput?: boolean
+}
+/**
+ * Brings `hwnd` to the foreground, restoring it first if minimized. Windows
+ * may refuse; the result says why. Throws only for an invalid handle.
+ */
+export declare function focusWindow(hwnd: number, options?: FocusOptions | undefined | null): FocusResult
+/**
+ * Like `focus_window`, but waits up to `timeoutMs` for `hwnd` to actually
+ * become the foreground window, so keystrokes sent next land in it.
+ * Returns whether it did. Throws only for an invalid handle.
+ */
+export declare function focusWindowSync(hwnd: number, timeoutMs: number, options?: FocusOptions | undefined | null): boolean
+/**
+ * Pins `hwnd` above all non-topmost windows, or releases it again, without
+ * moving or activating it. Windows of elevated apps refuse unless this
+ * process is elevated too.
+ */
+export declare function setWindowTopmost(hwnd: number, onTop: boolean): void
+/**
+ * Flashes `hwnd`'s taskbar button `count` times without taking focus, a
+ * quieter alternative to `focus_window` for notifications.
+ */
+export declare function flashWindow(hwnd: number, count: number): void
+/** The window's icon as a PNG, or `null` if it has none. */
+export declare function getWindowIcon(hwnd: number): Buffer | null
+/**
+ * A PNG screenshot of the whole window, frame included, e.g. of the overlay
+ * (via `WebviewHandle.get_hwnd`) or of a window DWM won't thumbnail. Works
+ * for windows covered by others, but minimized ones have nothing to show.
+ * `null` if the handle is invalid or the capture fails.
+ */
+export declare function captureWindow(hwnd: number): Buffer | null
+/**
+ * Tears down everything this module installed: stops every hotkey listener
+ * and removes the keyboard and mouse hooks. Also runs automatically when the Node
+ * environment exits.
+ */
+export declare function shutdown(): void
+/**
+ * The window the user is currently working in, or `null` if there is none
+ * (e.g. while focus is changing). Call it from a hotkey callback to remember
+ * where to return focus later.
+ */
+export declare function getForegroundWindow(): number | null
+/**
+ * The top-level window at screen point (`x`, `y`), e.g. under the cursor
+ * from `get_cursor_position`, or `null` over the desktop or one of this
+ * process's own windows such as the overlay. Hidden and disabled windows
+ * are skipped, as they are for mouse clicks.
+ */
+export declare function windowFromPoint(x: number, y: number): number | null
+/** Minimizes `hwnd` to the taskbar. */
+export declare function minimizeWindow(hwnd: number): void
+/** Maximizes `hwnd` on its current monitor. */
+export declare function maximizeWindow(hwnd: number): void
+/** Restores `hwnd` from minimized or maximized to its normal size and position. */
+export declare function restoreWindow(hwnd: number): void
+/**
+ * Moves and resizes `hwnd` to the given screen rectangle, restoring it first
+ * if minimized or maximized. With `visibleFrame` the rectangle describes the
+ * visible frame rather than the window rect, so the invisible borders don't
+ * leave gaps when snapping (e.g. a "left half" lines up with the monitor edge).
+ */
+export declare function moveWindow(hwnd: number, x: number, y: number, width: number, height: number, visibleFrame?: boolean | undefined | null): void
+/** One entry for `move_windows`, with the same meaning as `move_window`'s arguments */
+export interface WindowMove {
+  hwnd: number
+  x: number
+  y: number
+  width: number
+  height: number
+  visibleFrame?: boolean
+}
+/**
+ * Applies several `move_window` calls at once, e.g. for a tiling layout.
+ * The moves are batched with DeferWindowPos so the screen repaints once.
+ * Returns one flag per entry: false if that window was invalid or refused
+ * to move (an elevated window, say); the others still move.
+ */
+export declare function moveWindows(moves: Array<WindowMove>): Array<boolean>
+/** A window's bounds in screen coordinates */
+export interface WindowRect {
+  x: number
+  y: number
+  width: number
+  height: number
+}
+/**
+ * Where `hwnd` is on screen, or `null` if it isn't a window. With
+ * `visibleFrame` the bounds exclude the invisible borders, matching what the
+ * user sees and what `move_window` takes with the same flag.
+ */
+export declare function getWindowRect(hwnd: number, visibleFrame?: boolean | undefined | null): WindowRect | null
+/**
+ * Whether `hwnd` belongs to an elevated (high-integrity) process, or `null`
+ * if that can't be told (invalid handle, or access denied). An unelevated
+ * panepilot can't focus or hook into elevated windows, so the switcher can
+ * label or grey them out instead of silently failing.
+ */
+export declare function getWindowElevation(hwnd: number): boolean | null
+/**
+ * One display, in virtual-screen coordinates: physical pixels once the
+ * process is DPI aware (as `open_webview` makes it by default), otherwise
+ * DPI-scaled logical pixels
+ */
+export interface MonitorInfo {
+  x: number
+  y: number
+  width: number
+  height: number
+  workAreaX: number
+  workAreaY: number
+  workAreaWidth: number
+  workAreaHeight: number
+  scaleFactor: number
+  isPrimary: boolean
+}
+/**
+ * Lists all displays with their work areas and DPI scale factors. The scale
+ * factor falls back to 1.0 where per-monitor DPI isn't available.
+ */
+export declare function listMonitors(): Array<MonitorInfo>
+/** Payload passed to a mouse hook callback, copied from MSLLHOOKSTRUCT */
+export interface MouseEvent {
+  event: number
+  x: number
+  y: number
+  mouseData: number
+  time: number
+}
+/**
+ * Calls `callback` for every low-level mouse event (moves included) until
+ * `unregister_mouse_hook` and returns the hook's ID. Several hooks can be
+ * installed at once, each with its own thread. Keep the callback cheap:
+ * moves arrive at the pointer's report rate. If the hook can't be
+ * installed, the callback receives the error instead.
+ */
+export declare function registerMouseHook(callback: (...args: any[]) => any): number
+/**
+ * Removes the mouse hook `id` returned by `register_mouse_hook`, or every
+ * mouse hook when `id` is omitted. A no-op for hooks that are already gone.
+ */
+export declare function unregisterMouseHook(id?: number | undefined | null): void
+/** A point in virtual-screen coordinates, in the same pixels `list_monitors` uses */
+export interface CursorPosition {
+  x: number
+  y: number
+}
+/**
+ * Where the mouse cursor is right now, e.g. to open the overlay on the
+ * monitor under it. Fails while a secure desktop (UAC, lock screen) is up.
+ */
+export declare function getCursorPosition(): CursorPosition
+/**
+ * Shows a live preview of `sourceHwnd` inside `destHwnd` (e.g. the overlay,
+ * via `WebviewHandle.get_hwnd`) at `rect`, in the destination's client
+ * coordinates. The destination must be a top-level window of this process.
+ */
+export declare function registerWindowThumbnail(sourceHwnd: number, destHwnd: number, rect: WindowRect): ThumbnailHandle
+/** One step of a `send_keys` sequence */
+export interface KeyStroke {
+  vk: number
+  /** `true` presses the key, `false` releases it */
+  down: boolean
+}
+/**
+ * Injects `sequence` into the input stream in order, as if typed, so it
+ * reaches whichever window has the focus. Pair presses with releases or
+ * keys stay logically held. Windows silently drops input aimed at
+ * elevated windows (UIPI) unless this process is elevated too.
+ */
+export declare function sendKeys(sequence: Array<KeyStroke>): void
+/** Presses and releases `vk`; shorthand for a two-step `send_keys` */
+export declare function pressKey(vk: number): void
+/**
+ * The virtual-key code for a key name, or `null` if it isn't recognised.
+ * Accepts letters, digits, `F1`-`F24`, `Numpad0`-`Numpad9`, arrows
+ * (`Left`, `Up`, ...), media and volume keys (`MediaPlayPause`,
+ * `VolumeUp`, ...) and common named keys, ignoring case. Any other single
+ * character maps to the key that types it on the current keyboard layout.
+ * Numpad digits only reach `register_hotkey` while NumLock is on; with it
+ * off they arrive as navigation keys (`Numpad5` as `Clear`). Key hooks
+ * such as `register_key_release` match them either way.
+ */
+export declare function vkFromName(name: string): number | null
+/**
+ * A name for `vk`, or `null` for unknown codes. Built-in names round-trip
+ * through `vk_from_name`; other keys get the current layout's name for them.
+ */
+export declare function vkToName(vk: number): string | null
+/** Passed to the `set_log_callback` callback */
+export interface LogRecord {
+  /** `"error"`, `"warn"` or `"info"` */
+  level: string
+  message: string
+}
+/**
+ * Sends the module's diagnostics (failed registrations, hook errors, ...)
+ * to `callback` as `LogRecord`s instead of stderr; pass `null` to go back.
+ * The callback runs asynchronously on the event loop and doesn't keep the
+ * process alive.
+ */
+export declare function setLogCallback(callback?: (...args: any[]) => any | undefined | null): void
+/** Returned by `register_hotkey`/`register_hotkeys`; lets JS tear the hotkeys down again */
+export declare class HotkeyHandle {
+  /** ID of the first hotkey served by this handle */
+  get id(): number
+  /** IDs of every hotkey served by this handle, in registration order */
+  get ids(): Array<number>
+  /**
+   * OS thread running this handle's message loop; `null` before it has
+   * started and once it has exited, so it confirms `unregister` took effect
+   */
+  get threadId(): number | null
+  /**
+   * Stops the listener: its message loop exits and all its hotkeys are
+   * unregistered. Calling this more than once is a no-op.
+   */
+  unregister(): void
+}
 export declare class WebviewHandle {
+  /** Why the webview failed to build or stopped abnormally, if it did */
+  get error(): string | null
+  /** Unique per window for the life of the process, to tell windows apart */
+  get id(): number
+  /**
+   * Whether the window is gone: closed by the user or the OS, exited, or
+   * failed to build. Calls on a closed handle throw, except `exit`;
+   * `onEvent` reports the same moment as `closed`.
+   */
+  get closed(): boolean
   exit(): void
+  /** Sets the window caption; any Unicode text works */
   setTitle(title: string): void
   setVisible(visible: boolean): void
+  /** Resizes the window; both dimensions must be positive. */
+  setSize(width: number, height: number): void
+  /** Moves the window's top-left corner to (`x`, `y`) in screen coordinates. */
+  setPosition(x: number, y: number): void
+  /** Keeps the window above all non-topmost windows, or releases it again. */
+  setAlwaysOnTop(onTop: boolean): void
+  /**
+   * Moves the window behind all other windows, e.g. for a desktop-background
+   * style pane, without activating it. A topmost window loses that status.
+   */
+  sendToBottom(): void
+  /** Runs `js` in the page. Throws if the webview is gone or evaluation fails. */
+  eval(js: string): void
   setHtml(html: string): void
+  /**
+   * Sets the window's opacity, from 0.0 (invisible) to 1.0 (opaque). Values
+   * outside that range are clamped.
+   */
+  setOpacity(alpha: number): void
+  /**
+   * Lets mouse input pass through the window to whatever is underneath, or
+   * makes it clickable again. Opacity set with `setOpacity` is kept either way.
+   */
+  setClickThrough(enabled: boolean): void
+  /** Reloads the current page, e.g. to pick up changes from a dev server. */
+  reload(): void
+  /** Loads `url` in the existing window. */
+  navigate(url: string): void
+  /**
+   * Replaces the whole page with `html`, e.g. to swap a loading screen for
+   * the real UI. Unlike `setHtml` this rebuilds the document, so its
+   * `<head>` applies and its scripts run.
+   */
+  setContent(html: string): void
+  /**
+   * Brings the window to the foreground and gives it keyboard focus, so key
+   * events reach the page right after `setVisible(true)`. Throws if Windows
+   * refuses the foreground change.
+   */
+  focus(): void
+  /**
+   * Hides the window and, given `restoreHwnd` (e.g. from
+   * `getForegroundWindow` when the overlay was summoned), hands the
+   * foreground back to it. Throws if that handle is invalid or Windows
+   * refuses the foreground change.
+   */
+  dismiss(restoreHwnd?: number | undefined | null): void
+  /** Whether the window is currently shown. Throws if the webview is gone. */
+  isVisible(): boolean
+  /**
+   * Shows the window, then hides it again after `ms` milliseconds, e.g. for a
+   * HUD notification. Calling it again restarts the countdown; `setVisible`
+   * cancels it.
+   */
+  showFor(ms: number): void
+  /**
+   * The window's current screen rect, e.g. to save on close and pass back as
+   * `WebviewOptions.geometry`. Throws if the webview is gone.
+   */
+  getGeometry(): WindowRect
+  /**
+   * Centers the window in the work area of monitor `monitorIndex`, as
+   * numbered by `list_monitors`; out-of-range indexes are clamped.
+   */
+  centerOnMonitor(monitorIndex: number): void
+  /**
+   * Centers the window in the work area of the monitor `referenceHwnd` is
+   * on, e.g. the window that was focused when the overlay was summoned.
+   */
+  moveToWindowMonitor(referenceHwnd: number): void
+  /**
+   * The native window handle, for the `hwnd` parameters of this module
+   * (e.g. `register_window_thumbnail`) or other native code. It stays the
+   * same for the window's life. Throws if the webview is gone.
+   */
+  getHwnd(): number
+  /**
+   * Hides the window if it is shown and shows it otherwise, reading the real
+   * state on the webview thread; returns the new visibility. Cancels a
+   * pending `show_for` hide, as `set_visible` does.
+   */
+  toggleVisible(): boolean
+  /**
+   * Starts a native window drag as if the user had grabbed a title bar, for
+   * frameless windows with a custom header: call it from the page's
+   * `mousedown` (relayed through `window.external.invoke`) while the button
+   * is still held.
+   */
+  beginDrag(): void
+  /**
+   * Confines the mouse cursor to the window's current rect, e.g. while the
+   * overlay is in a modal selection mode. Call it again after moving or
+   * resizing the window. The cursor is freed by `release_cursor`, or when
+   * the window hides or closes. Throws if the window is hidden.
+   */
+  clipCursorToWindow(): void
+  /**
+   * Frees the cursor confined by `clip_cursor_to_window`. A no-op if this
+   * window doesn't hold the clip, so another app's clip is left alone.
+   */
+  releaseCursor(): void
+}
+/**
+ * Returned by `register_window_thumbnail`; the preview stays until
+ * `unregister`, the handle is garbage collected, or either window closes
+ */
+export declare class ThumbnailHandle {
+  /**
+   * Moves or resizes the preview within the destination window. DWM keeps
+   * the source's aspect ratio, fitting it inside `rect`.
+   */
+  setRect(rect: WindowRect): void
+  /** Removes the preview. Calling this more than once is a no-op. */
+  unregister(): void
 }
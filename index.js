@@ -310,10 +310,57 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { Modifiers, registerHotkey, WebviewHandle, openWebview, registerAltRelease } = nativeBinding
+const { Modifiers, HotkeyHandle, registerHotkeyEx, registerHotkey, registerHotkeys, isHotkeyAvailable, WebviewHandle, ContentKind, openWebview, registerKeyRelease, unregisterKeyRelease, registerAltRelease, listWindows, focusWindow, getWindowIcon, shutdown, getForegroundWindow, listMonitors, minimizeWindow, maximizeWindow, restoreWindow, moveWindow, registerMouseHook, unregisterMouseHook, isKeyDown, closeAllWebviews, vkFromName, vkToName, getWindowRect, getWindowElevation, KeyEventKind, flashWindow, openWebviewFromDir, cancelHotkey, getCursorPosition, moveWindows, FocusStatus, waitForKeyRelease, ThumbnailHandle, registerWindowThumbnail, suspendHotkeys, startHotkeyCapture, cancelHotkeyCapture, setWindowTopmost, focusWindowSync, sendKeys, pressKey, setLogCallback, registerHoldHotkey, windowFromPoint, captureWindow, registerDoubleTap } = nativeBinding
 
 module.exports.Modifiers = Modifiers
+module.exports.HotkeyHandle = HotkeyHandle
+module.exports.registerHotkeyEx = registerHotkeyEx
 module.exports.registerHotkey = registerHotkey
+module.exports.registerHotkeys = registerHotkeys
+module.exports.isHotkeyAvailable = isHotkeyAvailable
 module.exports.WebviewHandle = WebviewHandle
+module.exports.ContentKind = ContentKind
 module.exports.openWebview = openWebview
+module.exports.registerKeyRelease = registerKeyRelease
+module.exports.unregisterKeyRelease = unregisterKeyRelease
 module.exports.registerAltRelease = registerAltRelease
+module.exports.listWindows = listWindows
+module.exports.focusWindow = focusWindow
+module.exports.getWindowIcon = getWindowIcon
+module.exports.shutdown = shutdown
+module.exports.getForegroundWindow = getForegroundWindow
+module.exports.listMonitors = listMonitors
+module.exports.minimizeWindow = minimizeWindow
+module.exports.maximizeWindow = maximizeWindow
+module.exports.restoreWindow = restoreWindow
+module.exports.moveWindow = moveWindow
+module.exports.registerMouseHook = registerMouseHook
+module.exports.unregisterMouseHook = unregisterMouseHook
+module.exports.isKeyDown = isKeyDown
+module.exports.closeAllWebviews = closeAllWebviews
+module.exports.vkFromName = vkFromName
+module.exports.vkToName = vkToName
+module.exports.getWindowRect = getWindowRect
+module.exports.getWindowElevation = getWindowElevation
+module.exports.KeyEventKind = KeyEventKind
+module.exports.flashWindow = flashWindow
+module.exports.openWebviewFromDir = openWebviewFromDir
+module.exports.cancelHotkey = cancelHotkey
+module.exports.getCursorPosition = getCursorPosition
+module.exports.moveWindows = moveWindows
+module.exports.FocusStatus = FocusStatus
+module.exports.waitForKeyRelease = waitForKeyRelease
+module.exports.ThumbnailHandle = ThumbnailHandle
+module.exports.registerWindowThumbnail = registerWindowThumbnail
+module.exports.suspendHotkeys = suspendHotkeys
+module.exports.startHotkeyCapture = startHotkeyCapture
+module.exports.cancelHotkeyCapture = cancelHotkeyCapture
+module.exports.setWindowTopmost = setWindowTopmost
+module.exports.focusWindowSync = focusWindowSync
+module.exports.sendKeys = sendKeys
+module.exports.pressKey = pressKey
+module.exports.setLogCallback = setLogCallback
+module.exports.registerHoldHotkey = registerHoldHotkey
+module.exports.windowFromPoint = windowFromPoint
+module.exports.captureWindow = captureWindow
+module.exports.registerDoubleTap = registerDoubleTap
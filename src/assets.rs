@@ -0,0 +1,343 @@
+// --- Asset Server Section ---
+// A tiny loopback HTTP server so a webview can load a static web build from a
+// directory, which `file://` URLs make awkward (no fetch, module scripts, ...).
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::logging::{LogLevel, log};
+
+// Content types by file extension; anything else is served as octet-stream
+const CONTENT_TYPES: &[(&str, &str)] = &[
+  ("html", "text/html; charset=utf-8"),
+  ("htm", "text/html; charset=utf-8"),
+  ("js", "text/javascript; charset=utf-8"),
+  ("mjs", "text/javascript; charset=utf-8"),
+  ("css", "text/css; charset=utf-8"),
+  ("json", "application/json"),
+  ("map", "application/json"),
+  ("svg", "image/svg+xml"),
+  ("png", "image/png"),
+  ("jpg", "image/jpeg"),
+  ("jpeg", "image/jpeg"),
+  ("gif", "image/gif"),
+  ("ico", "image/x-icon"),
+  ("webp", "image/webp"),
+  ("woff", "font/woff"),
+  ("woff2", "font/woff2"),
+  ("ttf", "font/ttf"),
+  ("txt", "text/plain; charset=utf-8"),
+  ("wasm", "application/wasm"),
+];
+
+/// Serves files under `root` on `127.0.0.1` until dropped. Any local process
+/// can reach it while it runs, so only point it at files meant to be public.
+pub(crate) struct AssetServer {
+  port: u16,
+  stopped: Arc<AtomicBool>,
+}
+
+impl AssetServer {
+  /// Binds an OS-picked port and starts accepting on a background thread.
+  pub(crate) fn start(root: PathBuf) -> io::Result<Self> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let port = listener.local_addr()?.port();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let thread_stopped = stopped.clone();
+    let root = Arc::new(root);
+
+    thread::spawn(move || {
+      for stream in listener.incoming() {
+        if thread_stopped.load(Ordering::SeqCst) {
+          break;
+        }
+        match stream {
+          Ok(stream) => {
+            let root = root.clone();
+            thread::spawn(move || {
+              if let Err(e) = serve(&root, stream) {
+                log(
+                  LogLevel::Error,
+                  format!("Asset server request failed: {}", e),
+                );
+              }
+            });
+          }
+          Err(e) => log(
+            LogLevel::Error,
+            format!("Asset server accept failed: {}", e),
+          ),
+        }
+      }
+    });
+
+    Ok(AssetServer { port, stopped })
+  }
+
+  pub(crate) fn port(&self) -> u16 {
+    self.port
+  }
+}
+
+impl Drop for AssetServer {
+  fn drop(&mut self) {
+    self.stopped.store(true, Ordering::SeqCst);
+    // `accept` only notices the flag once it returns, so wake it up
+    let _ = TcpStream::connect_timeout(
+      &SocketAddr::from((Ipv4Addr::LOCALHOST, self.port)),
+      Duration::from_secs(1),
+    );
+  }
+}
+
+// Answers one request, then closes the connection
+fn serve(root: &Path, stream: TcpStream) -> io::Result<()> {
+  stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+  let mut reader = BufReader::new(stream.try_clone()?);
+  let mut request_line = String::new();
+  reader.read_line(&mut request_line)?;
+  // Skip the headers; nothing here depends on them
+  let mut header = String::new();
+  while reader.read_line(&mut header)? > 2 {
+    header.clear();
+  }
+
+  let mut parts = request_line.split_whitespace();
+  let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+  let mut stream = stream;
+  if method != "GET" && method != "HEAD" {
+    return respond(
+      &mut stream,
+      "405 Method Not Allowed",
+      "text/plain",
+      b"",
+      true,
+    );
+  }
+
+  let file = resolve(root, target).and_then(|path| fs::read(&path).ok().map(|body| (path, body)));
+  match file {
+    Some((path, body)) => {
+      let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+      let content_type = CONTENT_TYPES
+        .iter()
+        .find(|(ext, _)| *ext == extension)
+        .map_or("application/octet-stream", |(_, content_type)| content_type);
+      respond(&mut stream, "200 OK", content_type, &body, method == "GET")
+    }
+    None => respond(
+      &mut stream,
+      "404 Not Found",
+      "text/plain",
+      b"Not found",
+      method == "GET",
+    ),
+  }
+}
+
+fn respond(
+  stream: &mut TcpStream,
+  status: &str,
+  content_type: &str,
+  body: &[u8],
+  with_body: bool,
+) -> io::Result<()> {
+  write!(
+    stream,
+    "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+    status,
+    content_type,
+    body.len()
+  )?;
+  if with_body {
+    stream.write_all(body)?;
+  }
+  stream.flush()
+}
+
+// The file a request target serves: a directory means its `index.html`.
+// `join_target` keeps the path lexically under `root`; this also follows
+// symlinks and junctions, so one pointing outside the directory is refused.
+fn resolve(root: &Path, target: &str) -> Option<PathBuf> {
+  let path = join_target(root, target)?;
+  let path = if path.is_dir() {
+    path.join("index.html")
+  } else {
+    path
+  };
+  let canonical = path.canonicalize().ok()?;
+  canonical
+    .starts_with(root.canonicalize().ok()?)
+    .then_some(canonical)
+}
+
+// Maps a request target onto a path under `root`, refusing anything that
+// would climb out of it
+fn join_target(root: &Path, target: &str) -> Option<PathBuf> {
+  let path = target.split(['?', '#']).next().unwrap_or("");
+  let decoded = percent_decode(path)?;
+  let mut resolved = root.to_path_buf();
+  for segment in decoded.split('/') {
+    // Checked by hand so the guard doesn't depend on the host's path rules
+    if segment.contains(['\\', ':', '\0']) {
+      return None;
+    }
+    let mut components = Path::new(segment).components();
+    match (components.next(), components.next()) {
+      (None, _) | (Some(Component::CurDir), None) => {}
+      (Some(Component::Normal(name)), None) => resolved.push(name),
+      _ => return None, // "..", root and prefix components
+    }
+  }
+  Some(resolved)
+}
+
+fn percent_decode(input: &str) -> Option<String> {
+  let bytes = input.as_bytes();
+  let mut decoded = Vec::with_capacity(bytes.len());
+  let mut i = 0;
+  while i < bytes.len() {
+    if bytes[i] == b'%' {
+      let hex = bytes.get(i + 1..i + 3)?;
+      if !hex.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+      }
+      decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+      i += 3;
+    } else {
+      decoded.push(bytes[i]);
+      i += 1;
+    }
+  }
+  String::from_utf8(decoded).ok()
+}
+
+// Percent-encodes each `/`-separated segment of `path` for use in a URL, so
+// names with spaces, `#` or `?` reach `resolve` intact
+pub(crate) fn percent_encode_path(path: &str) -> String {
+  let mut encoded = String::with_capacity(path.len());
+  for byte in path.bytes() {
+    match byte {
+      b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+        encoded.push(byte as char)
+      }
+      _ => encoded.push_str(&format!("%{:02X}", byte)),
+    }
+  }
+  encoded
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn root() -> PathBuf {
+    PathBuf::from("site")
+  }
+
+  #[test]
+  fn resolves_paths_under_root() {
+    assert_eq!(join_target(&root(), "/"), Some(root()));
+    assert_eq!(
+      join_target(&root(), "/index.html"),
+      Some(root().join("index.html"))
+    );
+    assert_eq!(
+      join_target(&root(), "/js/./app.js?v=2#top"),
+      Some(root().join("js").join("app.js"))
+    );
+    assert_eq!(
+      join_target(&root(), "/my%20page.html"),
+      Some(root().join("my page.html"))
+    );
+  }
+
+  #[test]
+  fn refuses_parent_segments() {
+    assert_eq!(join_target(&root(), "/../secret.txt"), None);
+    assert_eq!(join_target(&root(), "/js/../../secret.txt"), None);
+    assert_eq!(join_target(&root(), "/%2e%2e/secret.txt"), None);
+    assert_eq!(join_target(&root(), "/%2E%2E/secret.txt"), None);
+    assert_eq!(join_target(&root(), "/.%2e/secret.txt"), None);
+  }
+
+  #[test]
+  fn refuses_encoded_separators() {
+    assert_eq!(join_target(&root(), "/js%2f..%2f..%2fsecret.txt"), None);
+    assert_eq!(join_target(&root(), "/js%5c..%5c..%5csecret.txt"), None);
+    assert_eq!(join_target(&root(), "/..%5csecret.txt"), None);
+  }
+
+  #[test]
+  fn refuses_absolute_paths() {
+    assert_eq!(join_target(&root(), "/C:/Windows/win.ini"), None);
+    assert_eq!(join_target(&root(), "/C%3a%5cWindows%5cwin.ini"), None);
+    assert_eq!(join_target(&root(), "/%5c%5cserver%5cshare"), None);
+    // A doubled slash is an empty segment, not a path from the drive root
+    assert_eq!(
+      join_target(&root(), "//etc/passwd"),
+      Some(root().join("etc").join("passwd"))
+    );
+  }
+
+  #[test]
+  fn refuses_nul_and_bad_escapes() {
+    assert_eq!(join_target(&root(), "/index.html%00.png"), None);
+    assert_eq!(percent_decode("%zz"), None);
+    assert_eq!(percent_decode("%2"), None);
+    assert_eq!(percent_decode("%+1"), None);
+    assert_eq!(percent_decode("%ff"), None);
+    assert_eq!(percent_decode("a%2Fb").as_deref(), Some("a/b"));
+  }
+
+  #[test]
+  fn encoded_entries_resolve_back() {
+    assert_eq!(
+      percent_encode_path("docs/my page #1?.html"),
+      "docs/my%20page%20%231%3F.html"
+    );
+    assert_eq!(percent_encode_path("caf\u{e9}.html"), "caf%C3%A9.html");
+    let entry = "docs/my page #1?.html";
+    assert_eq!(
+      join_target(&root(), &format!("/{}", percent_encode_path(entry))),
+      Some(root().join("docs").join("my page #1?.html"))
+    );
+  }
+
+  #[test]
+  fn refuses_symlinks_out_of_root() {
+    #[cfg(unix)]
+    use std::os::unix::fs::symlink as symlink_dir;
+    #[cfg(windows)]
+    use std::os::windows::fs::symlink_dir;
+
+    let base = std::env::temp_dir().join(format!("panepilot-assets-{}", std::process::id()));
+    let (site, outside) = (base.join("site"), base.join("outside"));
+    fs::create_dir_all(&site).unwrap();
+    fs::create_dir_all(&outside).unwrap();
+    fs::write(site.join("index.html"), "in").unwrap();
+    fs::write(outside.join("secret.txt"), "out").unwrap();
+    // Unprivileged Windows may refuse to create the link; nothing to check then
+    let linked = symlink_dir(&outside, site.join("link")).is_ok();
+
+    let site_index = site.join("index.html").canonicalize().unwrap();
+    assert_eq!(resolve(&site, "/"), Some(site_index.clone()));
+    assert_eq!(resolve(&site, "/index.html"), Some(site_index));
+    assert_eq!(resolve(&site, "/missing.html"), None);
+    if linked {
+      assert!(join_target(&site, "/link/secret.txt").is_some());
+      assert_eq!(resolve(&site, "/link/secret.txt"), None);
+    }
+    let _ = fs::remove_dir_all(&base);
+  }
+}
@@ -0,0 +1,103 @@
+// --- Image Section ---
+// Pixel helpers shared by icon and window capture.
+
+use windows::Win32::Graphics::Gdi::{
+  BI_RGB, BITMAP, BITMAPINFO, BITMAPINFOHEADER, DIB_RGB_COLORS, GetDC, GetDIBits, GetObjectW,
+  HBITMAP, HGDIOBJ, ReleaseDC,
+};
+
+/// Top-down 32-bit BGRA pixels read from a GDI bitmap
+pub(crate) struct Pixels {
+  pub width: u32,
+  pub height: u32,
+  pub bgra: Vec<u8>,
+}
+
+// Bytes in a `width` x `height` BGRA buffer, or None if that overflows
+pub(crate) fn bgra_len(width: u32, height: u32) -> Option<usize> {
+  (width as usize)
+    .checked_mul(height as usize)?
+    .checked_mul(4)
+}
+
+// Copies `bitmap` out as 32bpp top-down BGRA. Returns None if GDI refuses.
+pub(crate) fn read_bitmap(bitmap: HBITMAP) -> Option<Pixels> {
+  let mut info = BITMAP::default();
+  let read = unsafe {
+    GetObjectW(
+      HGDIOBJ(bitmap.0),
+      std::mem::size_of::<BITMAP>() as i32,
+      Some(&mut info as *mut BITMAP as *mut _),
+    )
+  };
+  if read == 0 || info.bmWidth <= 0 || info.bmHeight <= 0 {
+    return None;
+  }
+  let (width, height) = (info.bmWidth as u32, info.bmHeight as u32);
+  let len = bgra_len(width, height)?;
+
+  let mut header = BITMAPINFO {
+    bmiHeader: BITMAPINFOHEADER {
+      biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+      biWidth: width as i32,
+      biHeight: -(height as i32), // negative height = top-down rows
+      biPlanes: 1,
+      biBitCount: 32,
+      biCompression: BI_RGB.0,
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+  let mut bgra = vec![0u8; len];
+
+  let screen = unsafe { GetDC(None) };
+  let lines = unsafe {
+    GetDIBits(
+      screen,
+      bitmap,
+      0,
+      height,
+      Some(bgra.as_mut_ptr() as *mut _),
+      &mut header,
+      DIB_RGB_COLORS,
+    )
+  };
+  unsafe { ReleaseDC(None, screen) };
+  if lines == 0 {
+    return None;
+  }
+  Some(Pixels {
+    width,
+    height,
+    bgra,
+  })
+}
+
+// Encodes BGRA pixels as an RGBA PNG.
+pub(crate) fn encode_png(pixels: &Pixels) -> Option<Vec<u8>> {
+  let mut rgba = pixels.bgra.clone();
+  for pixel in rgba.chunks_exact_mut(4) {
+    pixel.swap(0, 2);
+  }
+
+  let mut out = Vec::new();
+  let mut encoder = png::Encoder::new(&mut out, pixels.width, pixels.height);
+  encoder.set_color(png::ColorType::Rgba);
+  encoder.set_depth(png::BitDepth::Eight);
+  let mut writer = encoder.write_header().ok()?;
+  writer.write_image_data(&rgba).ok()?;
+  writer.finish().ok()?;
+  Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn bgra_len_refuses_overflow() {
+    assert_eq!(bgra_len(2, 3), Some(24));
+    assert_eq!(bgra_len(0, 100), Some(0));
+    assert_eq!(bgra_len(u32::MAX, u32::MAX), None);
+  }
+}
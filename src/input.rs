@@ -0,0 +1,117 @@
+// --- Input Injection Section ---
+// Synthetic input for pilot actions, e.g. Ctrl+W in a freshly focused window.
+
+use std::mem::size_of;
+
+use napi::Result;
+use napi_derive::napi;
+
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+  INPUT, INPUT_0, INPUT_KEYBOARD, INPUT_MOUSE, KEYBD_EVENT_FLAGS, KEYBDINPUT,
+  KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, MAPVK_VK_TO_VSC, MOUSEEVENTF_MOVE, MOUSEINPUT,
+  MapVirtualKeyW, SendInput, VIRTUAL_KEY,
+};
+use windows::core::Error as WinError;
+
+// Keys that sit in the extended block; without the flag, apps reading scan
+// codes see e.g. numpad 4 instead of Left
+const EXTENDED_KEYS: &[u32] = &[
+  0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, // PageUp..Down
+  0x2C, 0x2D, 0x2E, // PrintScreen, Insert, Delete
+  0x5B, 0x5C, 0x5D, // LWin, RWin, Apps
+  0x6F, 0x90, // Divide, NumLock
+  0xA3, 0xA5, // RControl, RAlt
+  0xA6, 0xA7, 0xA8, 0xA9, 0xAA, 0xAB, 0xAC, // Browser keys
+  0xAD, 0xAE, 0xAF, 0xB0, 0xB1, 0xB2, 0xB3, // Volume and media keys
+  0xB4, 0xB5, 0xB6, 0xB7, // Launch keys
+];
+
+/// One step of a `send_keys` sequence
+#[napi(object)]
+pub struct KeyStroke {
+  pub vk: u32,
+  /// `true` presses the key, `false` releases it
+  pub down: bool,
+}
+
+fn key_input(stroke: &KeyStroke) -> Result<INPUT> {
+  let vk = u16::try_from(stroke.vk)
+    .ok()
+    .filter(|vk| (0x01..=0xFE).contains(vk))
+    .ok_or_else(|| {
+      napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Invalid virtual-key code: {}", stroke.vk),
+      )
+    })?;
+  let mut flags = KEYBD_EVENT_FLAGS(0);
+  if EXTENDED_KEYS.contains(&stroke.vk) {
+    flags |= KEYEVENTF_EXTENDEDKEY;
+  }
+  if !stroke.down {
+    flags |= KEYEVENTF_KEYUP;
+  }
+  Ok(INPUT {
+    r#type: INPUT_KEYBOARD,
+    Anonymous: INPUT_0 {
+      ki: KEYBDINPUT {
+        wVk: VIRTUAL_KEY(vk),
+        wScan: unsafe { MapVirtualKeyW(stroke.vk, MAPVK_VK_TO_VSC) } as u16,
+        dwFlags: flags,
+        time: 0,
+        dwExtraInfo: 0,
+      },
+    },
+  })
+}
+
+/// Injects `sequence` into the input stream in order, as if typed, so it
+/// reaches whichever window has the focus. Pair presses with releases or
+/// keys stay logically held. Windows silently drops input aimed at
+/// elevated windows (UIPI) unless this process is elevated too.
+#[napi]
+pub fn send_keys(sequence: Vec<KeyStroke>) -> Result<()> {
+  let inputs = sequence.iter().map(key_input).collect::<Result<Vec<_>>>()?;
+  if inputs.is_empty() {
+    return Ok(());
+  }
+  // One call keeps the sequence together; other input can't interleave
+  let sent = unsafe { SendInput(&inputs, size_of::<INPUT>() as i32) };
+  if sent as usize != inputs.len() {
+    return Err(napi::Error::new(
+      napi::Status::GenericFailure,
+      format!(
+        "SendInput injected {} of {} keystrokes: {}",
+        sent,
+        inputs.len(),
+        WinError::from_win32()
+      ),
+    ));
+  }
+  Ok(())
+}
+
+/// Presses and releases `vk`; shorthand for a two-step `send_keys`
+#[napi]
+pub fn press_key(vk: u32) -> Result<()> {
+  send_keys(vec![
+    KeyStroke { vk, down: true },
+    KeyStroke { vk, down: false },
+  ])
+}
+
+// Injects a mouse move of zero pixels: nothing visibly happens, but Windows
+// counts it as fresh input from this process, which SetForegroundWindow's
+// "did the caller just receive input" check looks for
+pub(crate) fn nudge_input() -> bool {
+  let input = INPUT {
+    r#type: INPUT_MOUSE,
+    Anonymous: INPUT_0 {
+      mi: MOUSEINPUT {
+        dwFlags: MOUSEEVENTF_MOVE,
+        ..Default::default()
+      },
+    },
+  };
+  unsafe { SendInput(&[input], size_of::<INPUT>() as i32) == 1 }
+}
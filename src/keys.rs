@@ -0,0 +1,260 @@
+// --- Key Name Section ---
+// Converts between virtual-key codes and the names config files use ("F24", "A").
+
+use napi_derive::napi;
+
+use windows::Win32::UI::Input::KeyboardAndMouse::{
+  GetKeyNameTextW, MAPVK_VK_TO_VSC, MapVirtualKeyW, VkKeyScanW,
+};
+
+// Canonical names for keys outside the letter, digit, F-key and numpad ranges
+const KEY_NAMES: &[(u32, &str)] = &[
+  (0x08, "Backspace"),
+  (0x09, "Tab"),
+  (0x0C, "Clear"), // Numpad5 with NumLock off
+  (0x0D, "Enter"),
+  (0x10, "Shift"),
+  (0x11, "Control"),
+  (0x12, "Alt"),
+  (0x13, "Pause"),
+  (0x14, "CapsLock"),
+  (0x1B, "Escape"),
+  (0x20, "Space"),
+  (0x21, "PageUp"),
+  (0x22, "PageDown"),
+  (0x23, "End"),
+  (0x24, "Home"),
+  (0x25, "Left"),
+  (0x26, "Up"),
+  (0x27, "Right"),
+  (0x28, "Down"),
+  (0x2C, "PrintScreen"),
+  (0x2D, "Insert"),
+  (0x2E, "Delete"),
+  (0x5B, "LWin"),
+  (0x5C, "RWin"),
+  (0x5D, "Apps"),
+  (0x6A, "Multiply"),
+  (0x6B, "Add"),
+  (0x6D, "Subtract"),
+  (0x6E, "Decimal"),
+  (0x6F, "Divide"),
+  (0x90, "NumLock"),
+  (0x91, "ScrollLock"),
+  (0xA0, "LShift"),
+  (0xA1, "RShift"),
+  (0xA2, "LControl"),
+  (0xA3, "RControl"),
+  (0xA4, "LAlt"),
+  (0xA5, "RAlt"),
+  (0xA6, "BrowserBack"),
+  (0xA7, "BrowserForward"),
+  (0xA8, "BrowserRefresh"),
+  (0xA9, "BrowserStop"),
+  (0xAA, "BrowserSearch"),
+  (0xAB, "BrowserFavorites"),
+  (0xAC, "BrowserHome"),
+  (0xAD, "VolumeMute"),
+  (0xAE, "VolumeDown"),
+  (0xAF, "VolumeUp"),
+  (0xB0, "MediaNextTrack"),
+  (0xB1, "MediaPrevTrack"),
+  (0xB2, "MediaStop"),
+  (0xB3, "MediaPlayPause"),
+  (0xB4, "LaunchMail"),
+  (0xB5, "LaunchMediaSelect"),
+  (0xB6, "LaunchApp1"),
+  (0xB7, "LaunchApp2"),
+  (0xBA, "Semicolon"),
+  (0xBB, "Equals"),
+  (0xBC, "Comma"),
+  (0xBD, "Minus"),
+  (0xBE, "Period"),
+  (0xBF, "Slash"),
+  (0xC0, "Backquote"),
+  (0xDB, "BracketLeft"),
+  (0xDC, "Backslash"),
+  (0xDD, "BracketRight"),
+  (0xDE, "Quote"),
+];
+
+// Extra spellings accepted by `vk_from_name`; never produced by `vk_to_name`
+const KEY_ALIASES: &[(u32, &str)] = &[
+  (0x0D, "Return"),
+  (0x11, "Ctrl"),
+  (0x1B, "Esc"),
+  (0x21, "PgUp"),
+  (0x22, "PgDn"),
+  (0x2D, "Ins"),
+  (0x2E, "Del"),
+  (0x5B, "Win"),
+  // KeyboardEvent.code names for the numpad operators
+  (0x6A, "NumpadMultiply"),
+  (0x6B, "NumpadAdd"),
+  (0x6D, "NumpadSubtract"),
+  (0x6E, "NumpadDecimal"),
+  (0x6F, "NumpadDivide"),
+  // KeyboardEvent.key names, for bindings recorded in a browser
+  (0xAD, "AudioVolumeMute"),
+  (0xAE, "AudioVolumeDown"),
+  (0xAF, "AudioVolumeUp"),
+  (0xB0, "MediaTrackNext"),
+  (0xB1, "MediaTrackPrevious"),
+];
+
+// Numpad keys report navigation codes while NumLock is off (or Shift is
+// held), e.g. VK_END for Numpad1 and VK_CLEAR for Numpad5. The dedicated
+// navigation keys send the same codes, but flagged as extended.
+const NUMPAD_NAVIGATION: &[(u32, u32)] = &[
+  (0x2D, 0x60), // Insert: Numpad0
+  (0x23, 0x61), // End
+  (0x28, 0x62), // Down
+  (0x22, 0x63), // PageDown
+  (0x25, 0x64), // Left
+  (0x0C, 0x65), // Clear
+  (0x27, 0x66), // Right
+  (0x24, 0x67), // Home
+  (0x26, 0x68), // Up
+  (0x21, 0x69), // PageUp: Numpad9
+  (0x2E, 0x6E), // Delete: Decimal
+];
+
+// The numpad key behind `vk` as reported with NumLock off, or `None` if
+// `vk` isn't one of those codes or came from the dedicated (`extended`) key
+pub(crate) fn numpad_key(vk: u32, extended: bool) -> Option<u32> {
+  if extended {
+    return None;
+  }
+  NUMPAD_NAVIGATION
+    .iter()
+    .find(|(navigation, _)| *navigation == vk)
+    .map(|(_, numpad)| *numpad)
+}
+
+fn table_name(vk: u32) -> Option<String> {
+  match vk {
+    0x30..=0x39 | 0x41..=0x5A => Some(char::from(vk as u8).to_string()),
+    0x60..=0x69 => Some(format!("Numpad{}", vk - 0x60)),
+    0x70..=0x87 => Some(format!("F{}", vk - 0x6F)),
+    _ => KEY_NAMES
+      .iter()
+      .find(|(code, _)| *code == vk)
+      .map(|(_, name)| name.to_string()),
+  }
+}
+
+fn table_code(name: &str) -> Option<u32> {
+  let upper = name.to_ascii_uppercase();
+  if let [c] = upper.as_bytes()
+    && c.is_ascii_alphanumeric()
+  {
+    return Some(*c as u32);
+  }
+  // "F1".."F24" and "Numpad0".."Numpad9"
+  let numbered = |prefix: &str, first: u32, last: u32| {
+    upper
+      .strip_prefix(prefix)
+      .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+      .and_then(|digits| digits.parse::<u32>().ok())
+      .filter(|n| (first..=last).contains(n))
+  };
+  if let Some(n) = numbered("F", 1, 24) {
+    return Some(0x6F + n);
+  }
+  if let Some(n) = numbered("NUMPAD", 0, 9) {
+    return Some(0x60 + n);
+  }
+  KEY_NAMES
+    .iter()
+    .chain(KEY_ALIASES)
+    .find(|(_, key)| key.eq_ignore_ascii_case(name))
+    .map(|(code, _)| *code)
+}
+
+/// The virtual-key code for a key name, or `null` if it isn't recognised.
+/// Accepts letters, digits, `F1`-`F24`, `Numpad0`-`Numpad9`, arrows
+/// (`Left`, `Up`, ...), media and volume keys (`MediaPlayPause`,
+/// `VolumeUp`, ...) and common named keys, ignoring case. Any other single
+/// character maps to the key that types it on the current keyboard layout.
+/// Numpad digits only reach `register_hotkey` while NumLock is on; with it
+/// off they arrive as navigation keys (`Numpad5` as `Clear`). Key hooks
+/// such as `register_key_release` match them either way.
+#[napi]
+pub fn vk_from_name(name: String) -> Option<u32> {
+  let name = name.trim();
+  if let Some(vk) = table_code(name) {
+    return Some(vk);
+  }
+  let mut chars = name.encode_utf16();
+  match (chars.next(), chars.next()) {
+    (Some(unit), None) => {
+      // Low byte is the key, high byte the shift state; -1 means no key types it
+      let scan = unsafe { VkKeyScanW(unit) };
+      (scan != -1).then_some((scan as u16 & 0xFF) as u32)
+    }
+    _ => None,
+  }
+}
+
+/// A name for `vk`, or `null` for unknown codes. Built-in names round-trip
+/// through `vk_from_name`; other keys get the current layout's name for them.
+#[napi]
+pub fn vk_to_name(vk: u32) -> Option<String> {
+  if let Some(name) = table_name(vk) {
+    return Some(name);
+  }
+  let scan_code = unsafe { MapVirtualKeyW(vk, MAPVK_VK_TO_VSC) };
+  if scan_code == 0 {
+    return None;
+  }
+  let mut buffer = [0u16; 64];
+  // GetKeyNameTextW takes the scan code in WM_KEYDOWN's lParam layout
+  let len = unsafe { GetKeyNameTextW((scan_code << 16) as i32, &mut buffer) };
+  (len > 0).then(|| String::from_utf16_lossy(&buffer[..len as usize]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn numpad_and_media_names_round_trip() {
+    for (name, vk) in [
+      ("Numpad0", 0x60),
+      ("Numpad5", 0x65),
+      ("Numpad9", 0x69),
+      ("Clear", 0x0C),
+      ("MediaPlayPause", 0xB3),
+      ("MediaNextTrack", 0xB0),
+      ("VolumeUp", 0xAF),
+      ("VolumeMute", 0xAD),
+      ("BrowserBack", 0xA6),
+      ("LaunchApp2", 0xB7),
+    ] {
+      assert_eq!(vk_from_name(name.to_string()), Some(vk), "{}", name);
+      assert_eq!(vk_to_name(vk).as_deref(), Some(name), "{:#04x}", vk);
+    }
+  }
+
+  #[test]
+  fn names_ignore_case_and_accept_browser_spellings() {
+    assert_eq!(vk_from_name("numpad5".to_string()), Some(0x65));
+    assert_eq!(vk_from_name(" mediaplaypause ".to_string()), Some(0xB3));
+    assert_eq!(vk_from_name("AudioVolumeUp".to_string()), Some(0xAF));
+    assert_eq!(vk_from_name("MediaTrackPrevious".to_string()), Some(0xB1));
+    assert_eq!(vk_from_name("NumpadAdd".to_string()), Some(0x6B));
+    // Aliases parse but never come back out
+    assert_eq!(vk_to_name(0xAF).as_deref(), Some("VolumeUp"));
+    assert_eq!(vk_from_name("Numpad10".to_string()), None);
+  }
+
+  #[test]
+  fn numpad_keys_match_with_numlock_off() {
+    assert_eq!(numpad_key(0x0C, false), Some(0x65)); // Clear: Numpad5
+    assert_eq!(numpad_key(0x23, false), Some(0x61)); // End: Numpad1
+    assert_eq!(numpad_key(0x2E, false), Some(0x6E)); // Delete: Decimal
+    // The dedicated navigation keys are extended and stay themselves
+    assert_eq!(numpad_key(0x23, true), None);
+    assert_eq!(numpad_key(0x41, false), None);
+  }
+}
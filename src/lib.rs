@@ -1,40 +1,78 @@
 #![allow(unused_imports)] // Keep this for now if needed
 
-use std::sync::{Arc, Mutex};
+mod assets;
+mod image;
+mod input;
+mod keys;
+mod logging;
+mod monitor;
+mod mouse;
+mod thumbnail;
+mod window;
+
+pub use input::*;
+pub use keys::*;
+pub use logging::*;
+pub use monitor::*;
+pub use mouse::*;
+pub use thumbnail::*;
+pub use window::*;
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
 use std::thread;
-use std::u32;
+use std::time::{Duration, Instant};
 
 // Import ErrorStrategy specifically
+use napi::bindgen_prelude::Either;
 use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use napi::{
-  CallContext, Env, Error as NapiError, JsFunction, JsObject, JsUndefined, NapiRaw, Result, Task,
+  CallContext, Env, Error as NapiError, JsDeferred, JsFunction, JsObject, JsUndefined, NapiRaw,
+  Result, Task,
 }; // Added NapiRaw for JsFunction context
 use napi_derive::napi;
 
 // pull hotkey registration from the KeyboardAndMouse module:
 use windows::Win32::UI::Input::KeyboardAndMouse::{
-  HOT_KEY_MODIFIERS, RegisterHotKey, UnregisterHotKey, VK_F24, VK_MENU,
+  GetAsyncKeyState, HOT_KEY_MODIFIERS, RegisterHotKey, ReleaseCapture, SetFocus, UnregisterHotKey,
+  VIRTUAL_KEY, VK_CONTROL, VK_F24, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
 };
 // pull message-loop pieces and WM_HOTKEY from WindowsAndMessaging:
 use windows::Win32::UI::WindowsAndMessaging::{
-  DispatchMessageW, GetMessageW, KBDLLHOOKSTRUCT_FLAGS, MSG, PostThreadMessageW, TranslateMessage,
-  WM_HOTKEY, WM_QUIT,
+  ClipCursor, DispatchMessageW, GetMessageW, HTCAPTION, HWND_BOTTOM, KBDLLHOOKSTRUCT_FLAGS,
+  KillTimer, LLKHF_EXTENDED, MSG, PM_NOREMOVE, PeekMessageW, PostThreadMessageW,
+  SET_WINDOW_POS_FLAGS, SendMessageW, SetTimer, SetWindowTextW, TranslateMessage, WM_HOTKEY,
+  WM_NCLBUTTONDOWN, WM_QUIT, WM_TIMER, WS_EX_APPWINDOW, WS_EX_TOOLWINDOW,
 };
 // Import necessary windows-rs types
 use windows::core::Error as WinError;
+use windows::core::HSTRING;
 use windows::core::Result as WinResult;
 
 // Import web_view types
 use web_view::{Content, Handle, WebView, builder}; // Keep Handle
 
+use assets::AssetServer;
+
 use once_cell::sync::Lazy;
 use std::ptr::null_mut;
-use windows::Win32::Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM};
-use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::Foundation::{
+  COLORREF, ERROR_HOTKEY_ALREADY_REGISTERED, HINSTANCE, HMODULE, HWND, LPARAM, LRESULT, WPARAM,
+};
+use windows::Win32::System::LibraryLoader::{
+  GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS, GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+  GetModuleHandleExW,
+};
 use windows::Win32::System::Threading::GetCurrentThreadId;
 use windows::Win32::UI::WindowsAndMessaging::{
-  CallNextHookEx, HC_ACTION, HHOOK, KBDLLHOOKSTRUCT, SetWindowsHookExW, UnhookWindowsHookEx,
-  WH_KEYBOARD_LL, WM_KEYUP, WM_SYSKEYUP,
+  CallNextHookEx, GWL_EXSTYLE, GetLayeredWindowAttributes, GetWindowLongPtrW, HC_ACTION, HHOOK,
+  HWND_NOTOPMOST, HWND_TOPMOST, IsWindowVisible, KBDLLHOOKSTRUCT, LAYERED_WINDOW_ATTRIBUTES_FLAGS,
+  LWA_ALPHA, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SetLayeredWindowAttributes,
+  SetWindowLongPtrW, SetWindowPos, SetWindowsHookExW, UnhookWindowsHookEx, WH_KEYBOARD_LL,
+  WINDOW_EX_STYLE, WM_KEYDOWN, WM_KEYUP, WM_SYSKEYDOWN, WM_SYSKEYUP, WS_EX_LAYERED,
+  WS_EX_TRANSPARENT,
 };
 
 use windows::core::PCWSTR;
@@ -57,104 +95,455 @@ fn modifiers_to_flags(modifier: Modifiers) -> u32 {
   }
 }
 
-/// background task that runs the Win32 message loop
-struct HotkeyListener {
+// All bits a caller may set in a raw modifier mask
+const MODIFIERS_MASK: u32 = 0x000F;
+// Suppresses repeated WM_HOTKEY while the combo is held (Windows 7+)
+const MOD_NOREPEAT: u32 = 0x4000;
+
+// Accepts either a bitmask (e.g. `Modifiers.Control | Modifiers.Alt`) or an
+// array of `Modifiers`. An empty array yields 0, i.e. a bare-VK hotkey;
+// duplicates are harmless since the flags are OR-ed together.
+fn resolve_modifiers(modifiers: Either<u32, Vec<Modifiers>>) -> Result<u32> {
+  match modifiers {
+    Either::A(mask) => {
+      if mask & !MODIFIERS_MASK != 0 {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Unknown modifier bits in mask: 0x{:X}", mask),
+        ));
+      }
+      Ok(mask)
+    }
+    Either::B(list) => Ok(
+      list
+        .into_iter()
+        .fold(0, |mask, modifier| mask | modifiers_to_flags(modifier)),
+    ),
+  }
+}
+
+/// Payload passed to a hotkey callback, decoded from the WM_HOTKEY message
+#[napi(object)]
+pub struct HotkeyEvent {
+  pub vk: u32,
+  pub modifiers: u32, // Win32 modifier flags, same bits as `Modifiers`
+  pub hotkey_id: i32,
+}
+
+/// State shared between a `HotkeyListener` and the `HotkeyHandle` returned to JS
+#[derive(Default)]
+struct ListenerControl {
+  thread_id: Option<u32>, // Set once the listener thread has a message queue
+  stopped: bool,          // Set by `unregister`, possibly before the thread started
+}
+
+type SharedListener = Arc<Mutex<ListenerControl>>;
+
+// Every live listener, keyed by hotkey ID (a batch shares one entry per ID),
+// so `shutdown` can stop them all
+static HOTKEY_LISTENERS: Lazy<Mutex<HashMap<i32, SharedListener>>> =
+  Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Asks a listener's message loop to exit; it unregisters its hotkeys on the way out.
+fn stop_listener(control: &SharedListener) -> WinResult<()> {
+  release_hold(control);
+  let mut control = control.lock().unwrap();
+  if control.stopped {
+    return Ok(());
+  }
+  control.stopped = true;
+  match control.thread_id.take() {
+    Some(tid) => unsafe { PostThreadMessageW(tid, WM_QUIT, WPARAM(0), LPARAM(0)) },
+    None => Ok(()), // not started yet; `compute` sees `stopped` and bails
+  }
+}
+
+// `register_hold_hotkey` pairs: the listener each key hook (by ID) lives
+// and dies with
+static HOLD_PAIRS: Lazy<Mutex<HashMap<u32, SharedListener>>> =
+  Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Removes the key hook paired with `control`, if it is a hold listener
+fn release_hold(control: &SharedListener) {
+  let hook_id = {
+    let mut pairs = HOLD_PAIRS.lock().unwrap();
+    let hook_id = pairs
+      .iter()
+      .find(|(_, listener)| Arc::ptr_eq(listener, control))
+      .map(|(hook_id, _)| *hook_id);
+    if let Some(hook_id) = hook_id {
+      pairs.remove(&hook_id);
+    }
+    hook_id
+  };
+  if let Some(hook_id) = hook_id {
+    remove_key_hooks(Some(hook_id));
+  }
+}
+
+// Stops the listener paired with key hook `hook_id`, if it has one
+fn unpair_hold(hook_id: u32) {
+  let control = HOLD_PAIRS.lock().unwrap().remove(&hook_id);
+  if let Some(control) = control {
+    let _ = stop_listener(&control);
+  }
+}
+
+type HotkeyCallback = ThreadsafeFunction<HotkeyEvent, ErrorStrategy::CalleeHandled>;
+
+/// One hotkey served by a `HotkeyListener`
+struct ListenerHotkey {
   hotkey_id: i32, // Use a specific ID for the hotkey
   mask: u32,      // Win32 modifier flags
   vk: u32,
-  tsfn: ThreadsafeFunction<(), ErrorStrategy::CalleeHandled>,
-  // Store the thread ID to post WM_QUIT later if needed (though NAPI handles task cancellation)
-  // thread_id: u32, // Uncomment if manual thread termination is needed
+  tsfn: HotkeyCallback,
+  debounce: Duration, // Fires closer together than this are dropped (zero = off)
+  last_fired: Option<Instant>, // When the callback was last called
+  scope_hwnd: Option<i64>, // Only fire while this window is in the foreground
+  lost: bool,         // `Watchdog` found the registration gone
 }
 
-impl Task for HotkeyListener {
-  type Output = ();
-  type JsValue = (); // Resolves to undefined in JS
+// Liveness pings from a listener's message loop, driven by a thread timer
+struct Heartbeat {
+  interval_ms: u32,
+  tsfn: ThreadsafeFunction<u32, ErrorStrategy::CalleeHandled>, // Gets the loop's message count
+}
 
-  fn compute(&mut self) -> Result<Self::Output> {
-    // Get the current thread ID if needed for WM_QUIT (optional)
-    // self.thread_id = unsafe { windows::Win32::System::Threading::GetCurrentThreadId() };
-
-    // Register the hotkey globally (hwnd = None)
-    let modifiers = HOT_KEY_MODIFIERS(self.mask);
-    // Use .is_ok() to check the Result<()> from RegisterHotKey
-    let registration_result: WinResult<()> =
-      unsafe { RegisterHotKey(None, self.hotkey_id, modifiers, self.vk) };
-
-    if registration_result.is_err() {
-      let error = WinError::from_win32(); // Get error info *after* failure
-      eprintln!(
-        "Failed to register hotkey (ID: {} Modifiers: {:?}, VK: {}): {:?}",
-        self.hotkey_id, modifiers, self.vk, error
-      );
-      return Err(napi::Error::new(
-        napi::Status::GenericFailure,
-        format!("Failed to register hotkey: {}", error),
-      ));
+type HotkeyIdCallback = ThreadsafeFunction<i32, ErrorStrategy::CalleeHandled>;
+
+// Periodic re-check that the OS still delivers the listener's hotkeys
+struct Watchdog {
+  interval_ms: u32,
+  on_lost: Option<HotkeyIdCallback>,
+  on_restored: Option<HotkeyIdCallback>,
+}
+
+// What a `run_message_loop` handler did with a message
+enum Pumped {
+  Handled,  // Consumed; not passed on
+  Dispatch, // Not ours; translate and dispatch it to its window, if any
+  Stop,     // Leave the loop
+}
+
+// Pumps this thread's messages into `handle` until WM_QUIT, a `Pumped::Stop`
+// or a GetMessageW failure, which is logged as coming from `name`. -1 is an
+// error, not a message: a plain truthiness check would spin on it.
+fn run_message_loop(name: &str, mut handle: impl FnMut(&MSG) -> Pumped) {
+  let mut msg = MSG::default();
+  loop {
+    match unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 {
+      -1 => {
+        log(
+          LogLevel::Error,
+          format!(
+            "Error in GetMessageW ({}): {:?}",
+            name,
+            WinError::from_win32()
+          ),
+        );
+        break;
+      }
+      0 => break, // WM_QUIT
+      _ => match handle(&msg) {
+        Pumped::Handled => {}
+        Pumped::Dispatch => unsafe {
+          let _ = TranslateMessage(&msg);
+          DispatchMessageW(&msg);
+        },
+        Pumped::Stop => break,
+      },
+    }
+  }
+}
+
+/// background task that runs the Win32 message loop for one or more hotkeys
+struct HotkeyListener {
+  hotkeys: Vec<ListenerHotkey>,
+  control: SharedListener, // Lets `HotkeyHandle::unregister` post WM_QUIT to this thread
+  retry_attempts: u32,     // Extra tries while another app still owns a combo
+  retry_delay: Duration,
+  max_call_failures: u32, // Consecutive failed callback calls before giving up
+  heartbeat: Option<Heartbeat>,
+  watchdog: Option<Watchdog>,
+}
+
+impl HotkeyListener {
+  // Drops this listener's entries from `HOTKEY_LISTENERS` once the task is done.
+  fn forget(&self) {
+    let mut listeners = HOTKEY_LISTENERS.lock().unwrap();
+    for hotkey in &self.hotkeys {
+      listeners.remove(&hotkey.hotkey_id);
     }
-    // println!("Hotkey registered successfully (ID: {})", self.hotkey_id);
+    drop(listeners);
+    // A listener that stopped on its own takes its hold hook with it
+    release_hold(&self.control);
+  }
 
-    let mut msg = MSG::default();
+  // Registers one hotkey. Only "already registered" is retried: it can clear
+  // up once another app finishes tearing down, anything else won't.
+  fn register(&self, hotkey: &ListenerHotkey) -> WinResult<()> {
+    let mut attempt = 0;
     loop {
-      // Blocking call
-      // GetMessageW returns > 0 for messages, 0 for WM_QUIT, -1 for error.
-      let result = unsafe { GetMessageW(&mut msg, None, 0, 0) };
-      match result.0 {
-        -1 => {
-          let error = WinError::from_win32();
-          eprintln!("Error in GetMessageW (ID: {}): {:?}", self.hotkey_id, error);
-          break; // Exit loop on error
-        }
-        0 => {
-          // Received WM_QUIT
-          println!(
-            "WM_QUIT received, exiting message loop (ID: {}).",
-            self.hotkey_id
+      let result = unsafe {
+        RegisterHotKey(
+          None,
+          hotkey.hotkey_id,
+          HOT_KEY_MODIFIERS(hotkey.mask),
+          hotkey.vk,
+        )
+      };
+      match result {
+        Err(error)
+          if win32_code(&error) == ERROR_HOTKEY_ALREADY_REGISTERED.0
+            && attempt < self.retry_attempts
+            && !self.control.lock().unwrap().stopped =>
+        {
+          attempt += 1;
+          log(
+            LogLevel::Warn,
+            format!(
+              "Hotkey (ID: {}) is already registered, retrying ({}/{}) in {:?}",
+              hotkey.hotkey_id, attempt, self.retry_attempts, self.retry_delay
+            ),
           );
-          break; // Exit loop cleanly
+          thread::sleep(self.retry_delay);
         }
-        _ => {
-          // Check if it's our hotkey message
-          // wParam for WM_HOTKEY is the hotkey ID (i32)
-          if msg.message == WM_HOTKEY && msg.wParam.0 as i32 == self.hotkey_id {
-            // Call the JS callback via the threadsafe function
-            let status = self
-              .tsfn
-              .call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
-            if status != napi::Status::Ok {
-              eprintln!(
-                "Failed to call JS callback (ID: {}): {:?}",
-                self.hotkey_id, status
-              );
-              // Consider if the loop should break here depending on desired behavior
-            }
-          } else {
-            // Only process other messages if necessary for this thread's function.
-            // For a pure hotkey listener, this might not be needed unless
-            // other windows/timers are created on this same thread.
-            // It's generally safe to include them.
-            unsafe {
-              let _ = TranslateMessage(&msg);
-              DispatchMessageW(&msg);
-            }
+        result => return result,
+      }
+    }
+  }
+
+  // Re-checks every registration for the `Watchdog`. Win32 can't be asked
+  // whether a hotkey is still registered, so the combo is registered again
+  // under a throwaway ID: "already registered" means it is still held, while
+  // success means it was lost. Only then is the real ID touched, so a live
+  // hotkey is never freed. A lost one is retried every tick.
+  fn verify_registrations(&mut self) {
+    let Some(watchdog) = &self.watchdog else {
+      return;
+    };
+    let notify = |tsfn: &Option<HotkeyIdCallback>, hotkey_id: i32| {
+      if let Some(tsfn) = tsfn {
+        let _ = tsfn.call(Ok(hotkey_id), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    };
+    let register = |id: i32, hotkey: &ListenerHotkey| unsafe {
+      RegisterHotKey(None, id, HOT_KEY_MODIFIERS(hotkey.mask), hotkey.vk)
+    };
+    let probe_id = match next_hotkey_id() {
+      Ok(id) => id,
+      Err(error) => {
+        log(LogLevel::Warn, format!("Skipping hotkey check: {}", error));
+        return;
+      }
+    };
+    for hotkey in &mut self.hotkeys {
+      let id = hotkey.hotkey_id;
+      if !hotkey.lost {
+        match register(probe_id, hotkey) {
+          Ok(()) => {
+            let _ = unsafe { UnregisterHotKey(None, probe_id) };
+            log(
+              LogLevel::Warn,
+              format!("Hotkey (ID: {}) is no longer registered", id),
+            );
+            hotkey.lost = true;
+            notify(&watchdog.on_lost, id);
+          }
+          // Still held, presumably by this listener
+          Err(error) if win32_code(&error) == ERROR_HOTKEY_ALREADY_REGISTERED.0 => continue,
+          Err(error) => {
+            log(
+              LogLevel::Warn,
+              format!("Could not check hotkey (ID: {}): {}", id, error),
+            );
+            continue;
           }
         }
       }
+      if register(id, hotkey).is_ok() {
+        hotkey.lost = false;
+        notify(&watchdog.on_restored, id);
+      }
     }
+  }
 
-    // --- Unregistration ---
-    // Use .is_ok() to check the Result<()> from UnregisterHotKey
-    let unregister_result: WinResult<()> = unsafe { UnregisterHotKey(None, self.hotkey_id) };
-    if unregister_result.is_err() {
-      let error = WinError::from_win32();
-      eprintln!(
-        "Failed to unregister hotkey (ID: {}): {:?}",
-        self.hotkey_id, error
+  // Unregisters every hotkey in `hotkeys`; safe for ones that never registered.
+  fn unregister_all(hotkeys: &[ListenerHotkey]) {
+    for hotkey in hotkeys.iter().filter(|hotkey| !hotkey.lost) {
+      // Use .is_ok() to check the Result<()> from UnregisterHotKey
+      let unregister_result: WinResult<()> = unsafe { UnregisterHotKey(None, hotkey.hotkey_id) };
+      if unregister_result.is_err() {
+        let error = WinError::from_win32();
+        log(
+          LogLevel::Error,
+          format!(
+            "Failed to unregister hotkey (ID: {}): {:?}",
+            hotkey.hotkey_id, error
+          ),
+        );
+        // Log error, maybe return an error if critical? Compute is about to finish anyway.
+      } else {
+        log(
+          LogLevel::Info,
+          format!(
+            "Hotkey unregistered successfully (ID: {})",
+            hotkey.hotkey_id
+          ),
+        );
+      }
+    }
+  }
+}
+
+impl Task for HotkeyListener {
+  type Output = ();
+  type JsValue = (); // Resolves to undefined in JS
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    // Force creation of this thread's message queue so a WM_QUIT posted by
+    // `HotkeyHandle::unregister` can't get lost, then publish the thread ID.
+    {
+      let mut msg = MSG::default();
+      let _ = unsafe { PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE) };
+      let mut control = self.control.lock().unwrap();
+      if control.stopped {
+        // Unregistered before the task even got a thread; nothing to do.
+        return Ok(());
+      }
+      control.thread_id = Some(unsafe { GetCurrentThreadId() });
+    }
+
+    // Register the hotkeys globally (hwnd = None). A batch is all-or-nothing.
+    for (index, hotkey) in self.hotkeys.iter().enumerate() {
+      let modifiers = HOT_KEY_MODIFIERS(hotkey.mask);
+      let registration_result: WinResult<()> = self.register(hotkey);
+
+      if let Err(error) = registration_result {
+        log(
+          LogLevel::Error,
+          format!(
+            "Failed to register hotkey (ID: {} Modifiers: {:?}, VK: {}): {:?}",
+            hotkey.hotkey_id, modifiers, hotkey.vk, error
+          ),
+        );
+        Self::unregister_all(&self.hotkeys[..index]);
+        self.control.lock().unwrap().thread_id = None;
+        // The task's promise isn't handed to JS, so report through the callback too.
+        hotkey.tsfn.call(
+          Err(registration_error(&error)),
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+        return Err(registration_error(&error));
+      }
+      // println!("Hotkey registered successfully (ID: {})", hotkey.hotkey_id);
+    }
+
+    // Thread timers (no window) post WM_TIMER straight to this loop
+    let start_timer = |interval_ms: u32| {
+      Some(unsafe { SetTimer(None, 0, interval_ms, None) }).filter(|&timer| timer != 0)
+    };
+    let heartbeat_timer = self
+      .heartbeat
+      .as_ref()
+      .and_then(|heartbeat| start_timer(heartbeat.interval_ms));
+    let watchdog_timer = self
+      .watchdog
+      .as_ref()
+      .and_then(|watchdog| start_timer(watchdog.interval_ms));
+
+    let mut failed_calls = 0;
+    let mut messages: u32 = 0;
+    run_message_loop("hotkey listener", |msg| {
+      messages = messages.wrapping_add(1);
+      if heartbeat_timer.is_some_and(|timer| msg.message == WM_TIMER && msg.wParam.0 == timer)
+        && let Some(heartbeat) = &self.heartbeat
+      {
+        let _ = heartbeat
+          .tsfn
+          .call(Ok(messages), ThreadsafeFunctionCallMode::NonBlocking);
+        return Pumped::Handled;
+      }
+      if watchdog_timer.is_some_and(|timer| msg.message == WM_TIMER && msg.wParam.0 == timer) {
+        self.verify_registrations();
+        return Pumped::Handled;
+      }
+
+      // Check if it's one of our hotkey messages
+      // wParam for WM_HOTKEY is the hotkey ID (i32)
+      let fired = if msg.message == WM_HOTKEY {
+        self
+          .hotkeys
+          .iter_mut()
+          .find(|hotkey| hotkey.hotkey_id == msg.wParam.0 as i32)
+      } else {
+        None
+      };
+      let Some(hotkey) = fired else {
+        return Pumped::Dispatch;
+      };
+      if HOTKEYS_SUSPENDED.load(Ordering::SeqCst) {
+        return Pumped::Handled; // `suspend_hotkeys`: swallow the combo, skip the callback
+      }
+      if hotkey
+        .scope_hwnd
+        .is_some_and(|scope| get_foreground_window() != Some(scope))
+      {
+        return Pumped::Handled; // Another window has focus; the combo is still consumed
+      }
+      let now = Instant::now();
+      if hotkey
+        .last_fired
+        .is_some_and(|last| now.duration_since(last) < hotkey.debounce)
+      {
+        return Pumped::Handled; // Debounced: too soon after the previous fire
+      }
+      hotkey.last_fired = Some(now);
+
+      // lParam packs the modifiers in the low word and the vk in the high word
+      let lparam = msg.lParam.0 as u32;
+      let event = HotkeyEvent {
+        vk: (lparam >> 16) & 0xFFFF,
+        modifiers: lparam & 0xFFFF,
+        hotkey_id: hotkey.hotkey_id,
+      };
+      // Call the JS callback via the threadsafe function
+      let status = hotkey
+        .tsfn
+        .call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+      if status == napi::Status::Ok {
+        failed_calls = 0;
+        return Pumped::Handled;
+      }
+      failed_calls += 1;
+      log(
+        LogLevel::Warn,
+        format!(
+          "Failed to call JS callback (ID: {}): {:?}",
+          hotkey.hotkey_id, status
+        ),
       );
-      // Log error, maybe return an error if critical? Compute is about to finish anyway.
-    } else {
-      println!("Hotkey unregistered successfully (ID: {})", self.hotkey_id);
+      // Typically `Closing`: the JS environment is gone and no later call
+      // can succeed, so stop instead of lingering as a zombie thread.
+      if failed_calls >= self.max_call_failures {
+        log(
+          LogLevel::Error,
+          format!(
+            "Giving up after {} failed callback calls, exiting hotkey message loop.",
+            failed_calls
+          ),
+        );
+        return Pumped::Stop;
+      }
+      Pumped::Handled
+    });
+
+    // --- Unregistration ---
+    for timer in [heartbeat_timer, watchdog_timer].into_iter().flatten() {
+      let _ = unsafe { KillTimer(None, timer) };
     }
+    self.control.lock().unwrap().thread_id = None;
+    Self::unregister_all(&self.hotkeys);
 
     // --- Cleanup ---
     // No explicit abort needed. Relies on RAII: tsfn will be dropped when
@@ -165,223 +554,2419 @@ impl Task for HotkeyListener {
 
   fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
     // Called on the main thread if `compute` succeeds.
+    self.forget();
     Ok(()) // Resolves to undefined in JS
   }
 
   fn reject(&mut self, _env: Env, err: napi::Error) -> Result<Self::JsValue> {
     // Called on the main thread if `compute` returns an Err.
-    eprintln!(
-      "HotkeyListener task failed (ID: {}): {}",
-      self.hotkey_id, err
+    log(
+      LogLevel::Error,
+      format!("HotkeyListener task failed: {}", err),
     );
+    self.forget();
     // Attempt unregistration *just in case*. Safe if not registered.
-    let _ = unsafe { UnregisterHotKey(None, self.hotkey_id) };
+    for hotkey in &self.hotkeys {
+      let _ = unsafe { UnregisterHotKey(None, hotkey.hotkey_id) };
+    }
     // No explicit abort needed (RAII).
-    Err(err) // Propagate the error so the JS Promise rejects
+    // `compute` only fails after handing the error to the callback, and the
+    // task's promise never reaches JS: rejecting it would surface as an
+    // unhandled rejection, which kills the process on Node 15+.
+    Ok(())
   }
 }
 
+// Extracts the Win32 error code from an HRESULT_FROM_WIN32-style error
+fn win32_code(error: &WinError) -> u32 {
+  let hresult = error.code().0 as u32;
+  if hresult & 0xFFFF_0000 == 0x8007_0000 {
+    hresult & 0xFFFF
+  } else {
+    hresult
+  }
+}
+
+// Maps a RegisterHotKey failure to a napi error JS can branch on: a combo
+// taken by another app (ERROR_HOTKEY_ALREADY_REGISTERED) is `InvalidArg`,
+// anything else `GenericFailure`. The raw code is always in the message.
+fn registration_error(error: &WinError) -> napi::Error {
+  let code = win32_code(error);
+  let status = if code == ERROR_HOTKEY_ALREADY_REGISTERED.0 {
+    napi::Status::InvalidArg
+  } else {
+    napi::Status::GenericFailure
+  };
+  napi::Error::new(
+    status,
+    format!("Failed to register hotkey (code {}): {}", code, error),
+  )
+}
+
 // Simple counter for unique hotkey IDs (ensures different calls get different IDs)
 static HOTKEY_ID_COUNTER: std::sync::atomic::AtomicI32 = std::sync::atomic::AtomicI32::new(1);
 
-#[napi]
-pub fn register_hotkey(env: Env, modifier: Modifiers, vk: u32, callback: JsFunction) -> Result<()> {
-  // Create a threadsafe function to call the JS callback from the listener thread.
-  let tsfn: ThreadsafeFunction<(), ErrorStrategy::CalleeHandled> = callback
-    .create_threadsafe_function(
-      0,
-      |ctx: napi::threadsafe_function::ThreadSafeCallContext<()>| {
-        // Map the Rust () unit type to JS 'undefined'.
-        // ctx.value is the () sent from the Rust side in tsfn.call(Ok(()), ...).
-        // We return a Vec<JsValue> to be passed as arguments to the JS callback.
-        Ok(vec![ctx.env.get_undefined()?]) // Send 'undefined' as the only argument
-      },
-    )?;
+// Highest ID an application may pass to RegisterHotKey
+const MAX_HOTKEY_ID: i32 = 0xBFFF;
 
-  // Generate a unique ID for this hotkey registration
-  let id = HOTKEY_ID_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-  // Get the correct Win32 modifier flags from the enum
-  let modifier_flags = modifiers_to_flags(modifier);
+// Shortest `heartbeatMs`; a liveness ping any more often only floods JS
+const MIN_HEARTBEAT_MS: u32 = 1000;
 
-  // Spawn the listener task on the libuv thread pool.
-  env.spawn(HotkeyListener {
-    hotkey_id: id,
-    mask: modifier_flags,
-    vk,
-    tsfn, // Move the threadsafe function into the task
-  })?; // This returns a Promise<void> in JS
+// Shortest `verifyMs`; each check registers a probe per hotkey, which is
+// pointless to do at timer resolution
+const MIN_VERIFY_MS: u32 = 250;
 
-  // println!(
-  //   "Attempting to register hotkey (ID: {}, Modifiers: 0x{:X}, VK: 0x{:X}) and spawn listener task.",
-  //   id, modifier_flags, vk
-  // );
-  Ok(()) // Return Ok(()) to indicate the spawning was successful (JS gets a Promise)
+// Next counter ID, wrapping within 1..=MAX_HOTKEY_ID and skipping any a live
+// listener uses, including IDs a caller picked explicitly
+fn next_hotkey_id() -> Result<i32> {
+  let listeners = HOTKEY_LISTENERS.lock().unwrap();
+  for _ in 0..MAX_HOTKEY_ID {
+    let id = HOTKEY_ID_COUNTER
+      .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |id| {
+        Some(id % MAX_HOTKEY_ID + 1)
+      })
+      .unwrap();
+    if !listeners.contains_key(&id) {
+      return Ok(id);
+    }
+  }
+  Err(napi::Error::new(
+    napi::Status::GenericFailure,
+    format!("All hotkey IDs up to {:#X} are in use", MAX_HOTKEY_ID),
+  ))
 }
 
-// --- WebView Section ---
+// Resolves the modifiers and applies the `noRepeat` default.
+fn hotkey_flags(modifiers: Either<u32, Vec<Modifiers>>, no_repeat: Option<bool>) -> Result<u32> {
+  // Get the correct Win32 modifier flags from the mask or list of enums
+  let mut modifier_flags = resolve_modifiers(modifiers)?;
+  // Held combos fire once by default; MOD_NOREPEAT needs Windows 7 or later.
+  if no_repeat.unwrap_or(true) {
+    modifier_flags |= MOD_NOREPEAT;
+  }
+  Ok(modifier_flags)
+}
+
+// Create a threadsafe function to call the JS callback from the listener thread.
+fn hotkey_callback(callback: JsFunction) -> Result<HotkeyCallback> {
+  callback.create_threadsafe_function(
+    0,
+    |ctx: napi::threadsafe_function::ThreadSafeCallContext<HotkeyEvent>| {
+      // ctx.value is the HotkeyEvent sent from the listener in tsfn.call(Ok(event), ...).
+      // We return a Vec of values to be passed as arguments to the JS callback.
+      Ok(vec![ctx.value]) // Send `{ vk, modifiers, hotkeyId }` as the only argument
+    },
+  )
+}
+
+/// Retries for a hotkey whose combo another app still holds, e.g. one that is
+/// shutting down as this one starts. Other registration errors fail at once.
+#[napi(object)]
+#[derive(Default)]
+pub struct RetryPolicy {
+  /// Extra registration attempts after the first (default 0: no retry)
+  pub attempts: Option<u32>,
+  /// Wait between attempts in milliseconds (default 250)
+  pub delay_ms: Option<u32>,
+}
+
+// Spawns the listener task on the libuv thread pool and wraps it in a handle.
+fn spawn_listener(
+  mut env: Env,
+  hotkeys: Vec<ListenerHotkey>,
+  retry: Option<RetryPolicy>,
+  max_call_failures: Option<u32>,
+  heartbeat: Option<Heartbeat>,
+  watchdog: Option<Watchdog>,
+) -> Result<HotkeyHandle> {
+  let retry = retry.unwrap_or_default();
+  ensure_cleanup_hook(&mut env)?;
+  let hotkey_ids = hotkeys.iter().map(|hotkey| hotkey.hotkey_id).collect();
+  let control: SharedListener = Arc::new(Mutex::new(ListenerControl::default()));
+  {
+    let mut listeners = HOTKEY_LISTENERS.lock().unwrap();
+    if let Some(taken) = hotkeys
+      .iter()
+      .find(|hotkey| listeners.contains_key(&hotkey.hotkey_id))
+    {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Hotkey ID {} is already in use", taken.hotkey_id),
+      ));
+    }
+    for hotkey in &hotkeys {
+      listeners.insert(hotkey.hotkey_id, control.clone());
+    }
+  }
+
+  env.spawn(HotkeyListener {
+    hotkeys, // Move the threadsafe functions into the task
+    control: control.clone(),
+    retry_attempts: retry.attempts.unwrap_or(0),
+    retry_delay: Duration::from_millis(retry.delay_ms.unwrap_or(250) as u64),
+    max_call_failures: max_call_failures.unwrap_or(3).max(1),
+    heartbeat,
+    watchdog,
+  })?;
 
-type SharedHandle = Arc<Mutex<Option<Handle<()>>>>;
+  Ok(HotkeyHandle {
+    hotkey_ids,
+    control,
+  })
+}
 
+/// Returned by `register_hotkey`/`register_hotkeys`; lets JS tear the hotkeys down again
 #[napi]
-pub struct WebviewHandle {
-  handle: SharedHandle,
+pub struct HotkeyHandle {
+  hotkey_ids: Vec<i32>,
+  control: SharedListener,
 }
 
 #[napi]
-impl WebviewHandle {
-  #[napi]
-  pub fn exit(&self) -> Result<()> {
-    if let Some(handle) = self.handle.lock().unwrap().take() {
-      let _ = handle.dispatch(|webview| {
-        webview.exit();
-        Ok(())
-      });
-    }
-    Ok(())
+impl HotkeyHandle {
+  /// ID of the first hotkey served by this handle
+  #[napi(getter)]
+  pub fn id(&self) -> i32 {
+    self.hotkey_ids[0]
   }
 
-  #[napi]
-  pub fn set_title(&self, title: String) -> Result<()> {
-    if let Some(handle) = self.handle.lock().unwrap().clone() {
-      let _ = handle.dispatch(move |webview| {
-        let _ = webview.set_title(&title);
-        Ok(())
-      });
-    }
-    Ok(())
+  /// IDs of every hotkey served by this handle, in registration order
+  #[napi(getter)]
+  pub fn ids(&self) -> Vec<i32> {
+    self.hotkey_ids.clone()
   }
 
-  #[napi]
-  pub fn set_visible(&self, visible: bool) -> Result<()> {
-    if let Some(handle) = self.handle.lock().unwrap().clone() {
-      let _ = handle.dispatch(move |webview| {
-        let _ = webview.set_visible(visible);
-        Ok(())
-      });
-    }
-    Ok(())
+  /// OS thread running this handle's message loop; `null` before it has
+  /// started and once it has exited, so it confirms `unregister` took effect
+  #[napi(getter)]
+  pub fn thread_id(&self) -> Option<u32> {
+    self.control.lock().unwrap().thread_id
   }
 
+  /// Stops the listener: its message loop exits and all its hotkeys are
+  /// unregistered. Calling this more than once is a no-op.
   #[napi]
-  pub fn set_html(&self, html: String) -> Result<()> {
-    if let Some(handle) = self.handle.lock().unwrap().clone() {
-      let _ = handle.dispatch(move |webview| {
-        // code below performs a redirect. bug or bad docs?
-        // webview.set_html(&html).unwrap_or_else(|e| {
-        //   eprintln!("Failed to set HTML in webview: {:?}", e);
-        // });
-        let escaped_html = serde_json::to_string(&html).unwrap();
-        let js_code = format!("document.documentElement.innerHTML = {};", escaped_html);
-        webview.eval(&js_code).unwrap_or_else(|e| {
-          eprintln!("Failed to set HTML in webview: {:?}", e);
-        });
-        Ok(())
-      });
-    }
-    Ok(())
+  pub fn unregister(&self) -> Result<()> {
+    stop_listener(&self.control).map_err(|error| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!(
+          "Failed to stop hotkey listener (IDs: {:?}): {}",
+          self.hotkey_ids, error
+        ),
+      )
+    })
   }
 }
 
+/// Options for `register_hotkey_ex`; omitted fields take the defaults below
+#[napi(object, object_to_js = false)]
+pub struct HotkeyOptions {
+  pub modifiers: Either<u32, Vec<Modifiers>>,
+  pub vk: u32,
+  /// Fixed hotkey ID, 0 to 0xBFFF, e.g. to keep it stable across restarts;
+  /// must not be in use by another live hotkey (default: a fresh ID)
+  pub id: Option<i32>,
+  /// Fire once while the combo is held (default true; needs Windows 7+)
+  pub no_repeat: Option<bool>,
+  /// Drop fires within this many ms of the previous one (default 0, off)
+  pub debounce_ms: Option<u32>,
+  /// Keep trying while another app still holds the combo (default: no retry)
+  pub retry: Option<RetryPolicy>,
+  /// Consecutive failed callback calls before giving up (default 3)
+  pub max_call_failures: Option<u32>,
+  /// Only fire while this window is in the foreground (default: anywhere)
+  pub scope_hwnd: Option<i64>,
+  /// Called every `heartbeatMs` from the listener thread with the number of
+  /// messages its loop has handled, to confirm it is alive (default: off)
+  pub on_heartbeat: Option<JsFunction>,
+  /// Heartbeat interval in milliseconds (default 60000, at least 1000)
+  pub heartbeat_ms: Option<u32>,
+  /// Re-check the registration this often, re-registering it if the OS
+  /// dropped it (default: off, at least 250)
+  pub verify_ms: Option<u32>,
+  /// Called with the hotkey ID when a check finds the registration gone
+  pub on_lost: Option<JsFunction>,
+  /// Called with the hotkey ID once a lost registration is back
+  pub on_restored: Option<JsFunction>,
+}
+
+/// Registers a global hotkey. `noRepeat` adds MOD_NOREPEAT so holding the
+/// combo fires the callback only once. `debounceMs` drops fires that follow
+/// the previous one too closely, e.g. an accidental double-tap. After
+/// `maxCallFailures` consecutive failed callback calls, e.g. once the JS
+/// environment is gone, the hotkey is unregistered for good.
+/// `scopeHwnd` limits the hotkey to one window, but only best-effort: the
+/// OS still registers it globally, so the combo is swallowed everywhere and
+/// merely ignored while another window is in the foreground.
+/// Some apps and OS events silently drop registrations; set `verifyMs` to
+/// have them re-checked and restored, reported via `onLost`/`onRestored`.
+/// If registration fails the callback receives the error: `code` is
+/// `InvalidArg` when another app already owns the combo (Win32 error 1409).
 #[napi]
-pub fn open_webview(title: String, width: i32, height: i32) -> Result<WebviewHandle> {
-  let handle_store: SharedHandle = Arc::new(Mutex::new(None));
-  let thread_store = handle_store.clone();
+pub fn register_hotkey_ex(
+  env: Env,
+  options: HotkeyOptions,
+  callback: JsFunction,
+) -> Result<HotkeyHandle> {
+  let mask = hotkey_flags(options.modifiers, options.no_repeat)?;
+  let tsfn = hotkey_callback(callback)?;
+  let heartbeat = match (options.on_heartbeat, options.heartbeat_ms) {
+    (None, Some(_)) => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "heartbeatMs needs an onHeartbeat callback".to_string(),
+      ));
+    }
+    (Some(_), Some(interval_ms)) if interval_ms < MIN_HEARTBEAT_MS => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!(
+          "heartbeatMs must be at least {}, got {}",
+          MIN_HEARTBEAT_MS, interval_ms
+        ),
+      ));
+    }
+    (Some(on_heartbeat), interval_ms) => Some(Heartbeat {
+      interval_ms: interval_ms.unwrap_or(60_000),
+      tsfn: on_heartbeat.create_threadsafe_function(
+        0,
+        |ctx: napi::threadsafe_function::ThreadSafeCallContext<u32>| Ok(vec![ctx.value]),
+      )?,
+    }),
+    (None, None) => None,
+  };
+  let id_callback = |callback: Option<JsFunction>| {
+    callback
+      .map(|callback| {
+        callback.create_threadsafe_function(
+          0,
+          |ctx: napi::threadsafe_function::ThreadSafeCallContext<i32>| Ok(vec![ctx.value]),
+        )
+      })
+      .transpose()
+  };
+  let watchdog = match options.verify_ms {
+    Some(interval_ms) if interval_ms < MIN_VERIFY_MS => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!(
+          "verifyMs must be at least {}, got {}",
+          MIN_VERIFY_MS, interval_ms
+        ),
+      ));
+    }
+    Some(interval_ms) => Some(Watchdog {
+      interval_ms,
+      on_lost: id_callback(options.on_lost)?,
+      on_restored: id_callback(options.on_restored)?,
+    }),
+    None => None,
+  };
+  let hotkey_id = match options.id {
+    Some(id) if !(0..=MAX_HOTKEY_ID).contains(&id) => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!(
+          "Hotkey ID must be between 0 and {:#X}, got {}",
+          MAX_HOTKEY_ID, id
+        ),
+      ));
+    }
+    Some(id) => id,
+    // Generate a unique ID for this hotkey registration
+    None => next_hotkey_id()?,
+  };
 
-  thread::spawn(move || {
-    let webview = builder()
-      .title(&title)
-      .content(Content::Html("<h1>Hello world!</h1>"))
-      .size(width, height)
-      .resizable(false)
-      .frameless(true) 
-      .debug(false)
-      .user_data(())
-      .invoke_handler(|_webview, _arg| Ok(()))
-      .visible(false)
-      .build()
-      .unwrap();
+  // println!(
+  //   "Attempting to register hotkey (ID: {}, Modifiers: 0x{:X}, VK: 0x{:X}) and spawn listener task.",
+  //   hotkey_id, mask, vk
+  // );
+  spawn_listener(
+    env,
+    vec![ListenerHotkey {
+      hotkey_id,
+      mask,
+      vk: options.vk,
+      tsfn,
+      debounce: Duration::from_millis(options.debounce_ms.unwrap_or(0) as u64),
+      last_fired: None,
+      scope_hwnd: options.scope_hwnd,
+      lost: false,
+    }],
+    options.retry,
+    options.max_call_failures,
+    heartbeat,
+    watchdog,
+  )
+}
 
-    let handle = webview.handle();
-    *thread_store.lock().unwrap() = Some(handle.clone());
+/// Registers a global hotkey with the defaults of `register_hotkey_ex`, which
+/// takes every other option (`noRepeat`, `debounceMs`, `retry`, ...).
+#[napi]
+pub fn register_hotkey(
+  env: Env,
+  modifiers: Either<u32, Vec<Modifiers>>,
+  vk: u32,
+  callback: JsFunction,
+) -> Result<HotkeyHandle> {
+  let options = HotkeyOptions {
+    modifiers,
+    vk,
+    id: None,
+    no_repeat: None,
+    debounce_ms: None,
+    retry: None,
+    max_call_failures: None,
+    scope_hwnd: None,
+    on_heartbeat: None,
+    heartbeat_ms: None,
+    verify_ms: None,
+    on_lost: None,
+    on_restored: None,
+  };
+  register_hotkey_ex(env, options, callback)
+}
 
-    webview.run().unwrap();
-  });
+/// One entry for `register_hotkeys`
+#[napi(object, object_to_js = false)]
+pub struct HotkeyBinding {
+  pub modifiers: Either<u32, Vec<Modifiers>>,
+  pub vk: u32,
+  pub callback: JsFunction,
+  pub no_repeat: Option<bool>,
+  pub debounce_ms: Option<u32>,
+  pub scope_hwnd: Option<i64>,
+}
 
-  Ok(WebviewHandle {
-    handle: handle_store,
-  })
+/// Registers several hotkeys served by a single listener thread. The batch is
+/// all-or-nothing: if any combo fails to register, none stay registered and
+/// that entry's callback receives the error. `retry` applies to each combo;
+/// `maxCallFailures` counts failed calls across all of them.
+#[napi]
+pub fn register_hotkeys(
+  env: Env,
+  bindings: Vec<HotkeyBinding>,
+  retry: Option<RetryPolicy>,
+  max_call_failures: Option<u32>,
+) -> Result<HotkeyHandle> {
+  if bindings.is_empty() {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      "register_hotkeys needs at least one binding".to_string(),
+    ));
+  }
+
+  let mut hotkeys = Vec::with_capacity(bindings.len());
+  for binding in bindings {
+    hotkeys.push(ListenerHotkey {
+      hotkey_id: next_hotkey_id()?,
+      mask: hotkey_flags(binding.modifiers, binding.no_repeat)?,
+      vk: binding.vk,
+      tsfn: hotkey_callback(binding.callback)?,
+      debounce: Duration::from_millis(binding.debounce_ms.unwrap_or(0) as u64),
+      last_fired: None,
+      scope_hwnd: binding.scope_hwnd,
+      lost: false,
+    });
+  }
+  spawn_listener(env, hotkeys, retry, max_call_failures, None, None)
 }
 
-#[derive(Copy, Clone)]
-struct SafeHhook(HHOOK);
-unsafe impl Send for SafeHhook {}
-unsafe impl Sync for SafeHhook {}
+/// Checks whether Windows would accept this combo right now by registering it
+/// under a throwaway ID and immediately unregistering it. Hotkeys already
+/// registered through `register_hotkey` are never touched.
+#[napi]
+pub fn is_hotkey_available(modifiers: Either<u32, Vec<Modifiers>>, vk: u32) -> Result<bool> {
+  let mask = resolve_modifiers(modifiers)?;
+  let probe_id = next_hotkey_id()?;
+  let registered = unsafe { RegisterHotKey(None, probe_id, HOT_KEY_MODIFIERS(mask), vk) }.is_ok();
+  if registered {
+    let _ = unsafe { UnregisterHotKey(None, probe_id) };
+  }
+  Ok(registered)
+}
 
-static HOOK_HANDLE: Lazy<Mutex<Option<SafeHhook>>> = Lazy::new(|| Mutex::new(None));
-static CALLBACK: Lazy<Mutex<Option<ThreadsafeFunction<(), ErrorStrategy::CalleeHandled>>>> =
-  Lazy::new(|| Mutex::new(None));
-static HOOK_THREAD_ID: Lazy<Mutex<Option<u32>>> = Lazy::new(|| Mutex::new(None));
+// Set by `suspend_hotkeys`; every listener checks it before firing
+static HOTKEYS_SUSPENDED: AtomicBool = AtomicBool::new(false);
 
-extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
-  unsafe {
-    if code == HC_ACTION as i32 && wparam.0 as u32 == 257 {
-      let kb = *(lparam.0 as *const KBDLLHOOKSTRUCT);
-      if kb.vkCode == 164 {
-        // fire callback once
-        if let Some(tsfn) = CALLBACK.lock().unwrap().take() {
-          let _ = tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
-        }
+/// Pauses (`true`) or resumes (`false`) every hotkey callback, e.g. while a
+/// game runs. The combos stay registered, so no other app can take them in
+/// the meantime; presses while suspended are swallowed and never queued.
+#[napi]
+pub fn suspend_hotkeys(suspended: bool) {
+  HOTKEYS_SUSPENDED.store(suspended, Ordering::SeqCst);
+}
 
-        // unhook
-        if let Some(SafeHhook(h)) = HOOK_HANDLE.lock().unwrap().take() {
-          let _ = UnhookWindowsHookEx(h);
-        }
-        // signal thread to exit
-        if let Some(tid) = HOOK_THREAD_ID.lock().unwrap().take() {
-          let _ = PostThreadMessageW(tid, WM_QUIT, WPARAM(0), LPARAM(0));
-        }
-      }
-    }
-    CallNextHookEx(None, code, wparam, lparam)
-  }
+/// Stops the listener serving hotkey `id` without needing its `HotkeyHandle`,
+/// e.g. when only the ID was kept. A batch listener stops as a whole. Returns
+/// false if no live listener serves that ID.
+#[napi]
+pub fn cancel_hotkey(id: i32) -> Result<bool> {
+  let control = HOTKEY_LISTENERS.lock().unwrap().get(&id).cloned();
+  let Some(control) = control else {
+    return Ok(false);
+  };
+  stop_listener(&control).map_err(|error| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to stop hotkey listener (ID: {}): {}", id, error),
+    )
+  })?;
+  Ok(true)
 }
 
+/// Tears down everything this module installed: stops every hotkey listener
+/// and removes the keyboard and mouse hooks. Also runs automatically when the Node
+/// environment exits.
 #[napi]
-pub fn register_alt_release(_env: Env, callback: JsFunction) -> Result<()> {
-  // prevent double registration
-  if HOOK_HANDLE.lock().unwrap().is_some() {
-    return Err(NapiError::from_reason(
-      "Hook already registered".to_string(),
-    ));
+pub fn shutdown() -> Result<()> {
+  let listeners: Vec<SharedListener> = HOTKEY_LISTENERS
+    .lock()
+    .unwrap()
+    .drain()
+    .map(|(_, control)| control)
+    .collect();
+  for control in &listeners {
+    // batch listeners appear once per hotkey; repeated stops are no-ops
+    let _ = stop_listener(control);
   }
-  if HOOK_THREAD_ID.lock().unwrap().is_some() {
-    return Err(NapiError::from_reason(
-      "Hook thread already running".to_string(),
-    ));
+
+  unregister_mouse_hook(None)?;
+  unregister_key_release(None)
+}
+
+static CLEANUP_HOOK_ADDED: AtomicBool = AtomicBool::new(false);
+
+// Arranges for `shutdown` to run when the Node environment exits. Called by
+// everything that installs a hotkey or hook; only the first call registers.
+fn ensure_cleanup_hook(env: &mut Env) -> Result<()> {
+  if !CLEANUP_HOOK_ADDED.swap(true, Ordering::SeqCst) {
+    env.add_env_cleanup_hook((), |_| {
+      let _ = shutdown();
+    })?;
   }
+  Ok(())
+}
 
-  let tsfn = callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.env.get_undefined()?]))?;
-  *CALLBACK.lock().unwrap() = Some(tsfn);
+// --- WebView Section ---
 
-  thread::spawn(move || unsafe {
-    let tid = GetCurrentThreadId();
-    *HOOK_THREAD_ID.lock().unwrap() = Some(tid);
-    let hmod = GetModuleHandleW(PCWSTR::null()).unwrap_or_default();
-    let hook = SetWindowsHookExW(
-      WH_KEYBOARD_LL,
-      Some(keyboard_proc),
-      Some(HINSTANCE(hmod.0)),
-      0,
-    )
-    .unwrap_or_else(|e| panic!("SetWindowsHookExW failed: {:?}", e));
-    *HOOK_HANDLE.lock().unwrap() = Some(SafeHhook(hook));
+// Work for the webview thread, queued until its handle exists
+type PendingCall = Box<dyn FnOnce(&mut WebView<()>) + Send>;
 
-    let mut msg = MSG::default();
-    while GetMessageW(&mut msg, None, 0, 0).into() {
-      let _ = TranslateMessage(&msg);
-      DispatchMessageW(&msg);
+// Where a webview is in its life; guarded by the `SharedHandle` mutex
+enum WebviewState {
+  Starting(Vec<PendingCall>), // Still building; calls wait here, in order
+  Running(Handle<()>),
+  Closed, // Exited, failed to build, or asked to exit
+}
+
+type SharedHandle = Arc<Mutex<WebviewState>>;
+// Why the webview thread gave up, if it did
+type SharedError = Arc<Mutex<Option<String>>>;
+
+#[napi]
+pub struct WebviewHandle {
+  id: u32,
+  handle: SharedHandle,
+  error: SharedError,
+  hide_generation: Arc<AtomicU32>, // Bumped to cancel a pending `show_for` hide
+}
+
+// Every webview whose thread is still alive, keyed by id, so
+// `close_all_webviews` can reach windows JS no longer holds a handle to
+static WEBVIEWS: Lazy<Mutex<HashMap<u32, SharedHandle>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static WEBVIEW_ID_COUNTER: AtomicU32 = AtomicU32::new(1);
+
+// Runs `f` on the webview thread: right away if it's running, once it's up
+// if it's still starting. Returns false if the webview has closed.
+fn dispatch_to<F>(store: &SharedHandle, f: F) -> bool
+where
+  F: FnOnce(&mut WebView<()>) + Send + 'static,
+{
+  match &mut *store.lock().unwrap() {
+    WebviewState::Starting(pending) => {
+      pending.push(Box::new(f));
+      true
     }
-  });
+    WebviewState::Running(handle) => handle
+      .dispatch(move |webview| {
+        f(webview);
+        Ok(())
+      })
+      .is_ok(),
+    WebviewState::Closed => false,
+  }
+}
 
-  Ok(())
+// Asks the webview behind `store` to close; later calls find it gone
+fn exit_webview(store: &SharedHandle) {
+  let state = std::mem::replace(&mut *store.lock().unwrap(), WebviewState::Closed);
+  if let WebviewState::Running(handle) = state {
+    let _ = handle.dispatch(|webview| {
+      webview.exit();
+      Ok(())
+    });
+  }
+  // Still starting: the webview thread sees `Closed` once built and stops
+}
+
+// The native window behind a webview; only valid on the webview thread
+fn webview_hwnd(webview: &WebView<()>) -> HWND {
+  HWND(webview.window_handle())
+}
+
+// Sets the caption through the wide-char API; the backend's own narrow-string
+// path can mangle emoji and CJK titles
+fn set_window_title(hwnd: HWND, title: &str) {
+  let _ = unsafe { SetWindowTextW(hwnd, &HSTRING::from(title)) };
+}
+
+// Sets, then clears, bits of a window's extended style
+fn update_ex_style(hwnd: HWND, set: WINDOW_EX_STYLE, clear: WINDOW_EX_STYLE) {
+  unsafe {
+    let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+    let _ = SetWindowLongPtrW(
+      hwnd,
+      GWL_EXSTYLE,
+      (style | set.0 as isize) & !(clear.0 as isize),
+    );
+  }
+}
+
+// Id of the webview `clip_cursor_to_window` confined the cursor to; 0 = none
+static CURSOR_CLIP_OWNER: AtomicU32 = AtomicU32::new(0);
+
+// Frees the cursor if webview `id` confined it. The clip is global, so a
+// hidden or closed overlay would otherwise strand the cursor in its rect.
+fn release_cursor_clip(id: u32) {
+  if CURSOR_CLIP_OWNER
+    .compare_exchange(id, 0, Ordering::SeqCst, Ordering::SeqCst)
+    .is_ok()
+  {
+    let _ = unsafe { ClipCursor(None) };
+  }
+}
+
+// Shows or hides a webview; hiding ends its cursor clip
+fn set_webview_visible(webview: &mut WebView<()>, id: u32, visible: bool) {
+  webview.set_visible(visible);
+  if !visible {
+    release_cursor_clip(id);
+  }
+}
+
+// How long a round-trip to the webview thread may take before we give up
+const DISPATCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl WebviewHandle {
+  // Centers the window, at its current size, in `monitor`'s work area
+  fn center_in_work_area(&self, monitor: &MonitorInfo) -> Result<()> {
+    let (area_x, area_y) = (monitor.work_area_x, monitor.work_area_y);
+    let (area_width, area_height) = (monitor.work_area_width, monitor.work_area_height);
+    self.dispatch(move |webview| {
+      let hwnd = webview_hwnd(webview);
+      let Some(rect) = window::window_rect(hwnd) else {
+        return;
+      };
+      let (width, height) = (rect.right - rect.left, rect.bottom - rect.top);
+      let _ = unsafe {
+        SetWindowPos(
+          hwnd,
+          None,
+          area_x + (area_width - width) / 2,
+          area_y + (area_height - height) / 2,
+          0,
+          0,
+          SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+      };
+    })
+  }
+
+  // Fire-and-forget `dispatch_to`; throws if the webview has closed, so JS
+  // finds out it is holding a dead handle
+  fn dispatch<F>(&self, f: F) -> Result<()>
+  where
+    F: FnOnce(&mut WebView<()>) + Send + 'static,
+  {
+    if dispatch_to(&self.handle, f) {
+      Ok(())
+    } else {
+      Err(self.closed_error())
+    }
+  }
+
+  // The error for calls on a closed webview, with the reason if it died
+  fn closed_error(&self) -> napi::Error {
+    let reason = self.error.lock().unwrap().clone();
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      reason.unwrap_or_else(|| "webview is closed".to_string()),
+    )
+  }
+
+  // Runs `f` on the webview thread and waits for its result. A webview that
+  // is still starting counts against the timeout.
+  fn call<R, F>(&self, f: F) -> Result<R>
+  where
+    R: Send + 'static,
+    F: FnOnce(&mut WebView<()>) -> R + Send + 'static,
+  {
+    let (tx, rx) = mpsc::channel();
+    let accepted = dispatch_to(&self.handle, move |webview| {
+      let _ = tx.send(f(webview));
+    });
+    if !accepted {
+      return Err(self.closed_error());
+    }
+    rx.recv_timeout(DISPATCH_TIMEOUT).map_err(|_| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        "webview did not respond".to_string(),
+      )
+    })
+  }
+}
+
+#[napi]
+impl WebviewHandle {
+  /// Why the webview failed to build or stopped abnormally, if it did
+  #[napi(getter)]
+  pub fn error(&self) -> Option<String> {
+    self.error.lock().unwrap().clone()
+  }
+
+  /// Unique per window for the life of the process, to tell windows apart
+  #[napi(getter)]
+  pub fn id(&self) -> u32 {
+    self.id
+  }
+
+  /// Whether the window is gone: closed by the user or the OS, exited, or
+  /// failed to build. Calls on a closed handle throw, except `exit`;
+  /// `onEvent` reports the same moment as `closed`.
+  #[napi(getter)]
+  pub fn closed(&self) -> bool {
+    matches!(*self.handle.lock().unwrap(), WebviewState::Closed)
+  }
+
+  #[napi]
+  pub fn exit(&self) -> Result<()> {
+    exit_webview(&self.handle);
+    Ok(())
+  }
+
+  /// Sets the window caption; any Unicode text works
+  #[napi]
+  pub fn set_title(&self, title: String) -> Result<()> {
+    if title.contains('\0') {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Webview title can't contain NUL characters".to_string(),
+      ));
+    }
+    self.dispatch(move |webview| set_window_title(webview_hwnd(webview), &title))
+  }
+
+  #[napi]
+  pub fn set_visible(&self, visible: bool) -> Result<()> {
+    // An explicit change wins over a pending `show_for` hide
+    self.hide_generation.fetch_add(1, Ordering::SeqCst);
+    let id = self.id;
+    self.dispatch(move |webview| set_webview_visible(webview, id, visible))
+  }
+
+  /// Resizes the window; both dimensions must be positive.
+  #[napi]
+  pub fn set_size(&self, width: i32, height: i32) -> Result<()> {
+    if width <= 0 || height <= 0 {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        format!("Invalid webview size: {}x{}", width, height),
+      ));
+    }
+    self.dispatch(move |webview| {
+      let _ = unsafe {
+        SetWindowPos(
+          webview_hwnd(webview),
+          None,
+          0,
+          0,
+          width,
+          height,
+          SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+      };
+    })
+  }
+
+  /// Moves the window's top-left corner to (`x`, `y`) in screen coordinates.
+  #[napi]
+  pub fn set_position(&self, x: i32, y: i32) -> Result<()> {
+    self.dispatch(move |webview| {
+      let _ = unsafe {
+        SetWindowPos(
+          webview_hwnd(webview),
+          None,
+          x,
+          y,
+          0,
+          0,
+          SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+      };
+    })
+  }
+
+  /// Keeps the window above all non-topmost windows, or releases it again.
+  #[napi]
+  pub fn set_always_on_top(&self, on_top: bool) -> Result<()> {
+    self.dispatch(move |webview| {
+      let insert_after = if on_top { HWND_TOPMOST } else { HWND_NOTOPMOST };
+      let _ = unsafe {
+        SetWindowPos(
+          webview_hwnd(webview),
+          Some(insert_after),
+          0,
+          0,
+          0,
+          0,
+          SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+      };
+    })
+  }
+
+  /// Moves the window behind all other windows, e.g. for a desktop-background
+  /// style pane, without activating it. A topmost window loses that status.
+  #[napi]
+  pub fn send_to_bottom(&self) -> Result<()> {
+    self.dispatch(|webview| {
+      let _ = unsafe {
+        SetWindowPos(
+          webview_hwnd(webview),
+          Some(HWND_BOTTOM),
+          0,
+          0,
+          0,
+          0,
+          SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+        )
+      };
+    })
+  }
+
+  /// Runs `js` in the page. Throws if the webview is gone or evaluation fails.
+  #[napi]
+  pub fn eval(&self, js: String) -> Result<()> {
+    self.call(move |webview| webview.eval(&js))?.map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to evaluate JavaScript in webview: {}", e),
+      )
+    })
+  }
+
+  #[napi]
+  pub fn set_html(&self, html: String) -> Result<()> {
+    self.dispatch(move |webview| {
+      // code below performs a redirect. bug or bad docs?
+      // webview.set_html(&html).unwrap_or_else(|e| {
+      //   eprintln!("Failed to set HTML in webview: {:?}", e);
+      // });
+      let escaped_html = serde_json::to_string(&html).unwrap();
+      let js_code = format!("document.documentElement.innerHTML = {};", escaped_html);
+      webview.eval(&js_code).unwrap_or_else(|e| {
+        log(
+          LogLevel::Error,
+          format!("Failed to set HTML in webview: {:?}", e),
+        );
+      });
+    })
+  }
+
+  /// Sets the window's opacity, from 0.0 (invisible) to 1.0 (opaque). Values
+  /// outside that range are clamped.
+  #[napi]
+  pub fn set_opacity(&self, alpha: f64) -> Result<()> {
+    if alpha.is_nan() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "Invalid webview opacity: NaN",
+      ));
+    }
+    let alpha = (alpha.clamp(0.0, 1.0) * 255.0).round() as u8;
+    self.dispatch(move |webview| {
+      let hwnd = webview_hwnd(webview);
+      // Layered-window alpha only applies once WS_EX_LAYERED is set
+      update_ex_style(hwnd, WS_EX_LAYERED, WINDOW_EX_STYLE(0));
+      let _ = unsafe { SetLayeredWindowAttributes(hwnd, COLORREF(0), alpha, LWA_ALPHA) };
+    })
+  }
+
+  /// Lets mouse input pass through the window to whatever is underneath, or
+  /// makes it clickable again. Opacity set with `setOpacity` is kept either way.
+  #[napi]
+  pub fn set_click_through(&self, enabled: bool) -> Result<()> {
+    self.dispatch(move |webview| {
+      let hwnd = webview_hwnd(webview);
+      let layered = unsafe { GetWindowLongPtrW(hwnd, GWL_EXSTYLE) } & WS_EX_LAYERED.0 as isize != 0;
+      if enabled {
+        // WS_EX_TRANSPARENT only skips hit-testing on layered windows
+        update_ex_style(hwnd, WS_EX_TRANSPARENT | WS_EX_LAYERED, WINDOW_EX_STYLE(0));
+        if !layered {
+          // A layered window isn't drawn until its attributes are set
+          let _ = unsafe { SetLayeredWindowAttributes(hwnd, COLORREF(0), 255, LWA_ALPHA) };
+        }
+      } else {
+        // Back to the original style, unless setOpacity still needs layering
+        let mut alpha = 255u8;
+        let mut flags = LAYERED_WINDOW_ATTRIBUTES_FLAGS(0);
+        let translucent = layered
+          && unsafe { GetLayeredWindowAttributes(hwnd, None, Some(&mut alpha), Some(&mut flags)) }
+            .is_ok()
+          && flags.contains(LWA_ALPHA)
+          && alpha < 255;
+        let clear = if translucent {
+          WS_EX_TRANSPARENT
+        } else {
+          WS_EX_TRANSPARENT | WS_EX_LAYERED
+        };
+        update_ex_style(hwnd, WINDOW_EX_STYLE(0), clear);
+      }
+    })
+  }
+
+  /// Reloads the current page, e.g. to pick up changes from a dev server.
+  #[napi]
+  pub fn reload(&self) -> Result<()> {
+    self.dispatch(move |webview| {
+      webview
+        .eval("window.location.reload();")
+        .unwrap_or_else(|e| {
+          log(
+            LogLevel::Error,
+            format!("Failed to reload webview: {:?}", e),
+          );
+        });
+    })
+  }
+
+  /// Loads `url` in the existing window.
+  #[napi]
+  pub fn navigate(&self, url: String) -> Result<()> {
+    if url.trim().is_empty() {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "navigate needs a non-empty URL".to_string(),
+      ));
+    }
+    self.dispatch(move |webview| {
+      // JSON string syntax is valid JS, so quotes/backslashes can't break out
+      let escaped_url = serde_json::to_string(&url).unwrap();
+      let js_code = format!("window.location.href = {};", escaped_url);
+      webview.eval(&js_code).unwrap_or_else(|e| {
+        log(
+          LogLevel::Error,
+          format!("Failed to navigate webview: {:?}", e),
+        );
+      });
+    })
+  }
+
+  /// Replaces the whole page with `html`, e.g. to swap a loading screen for
+  /// the real UI. Unlike `setHtml` this rebuilds the document, so its
+  /// `<head>` applies and its scripts run.
+  #[napi]
+  pub fn set_content(&self, html: String) -> Result<()> {
+    self.dispatch(move |webview| {
+      let escaped_html = serde_json::to_string(&html).unwrap();
+      let js_code = format!(
+        "document.open(); document.write({}); document.close();",
+        escaped_html
+      );
+      webview.eval(&js_code).unwrap_or_else(|e| {
+        log(
+          LogLevel::Error,
+          format!("Failed to set webview content: {:?}", e),
+        );
+      });
+    })
+  }
+
+  /// Brings the window to the foreground and gives it keyboard focus, so key
+  /// events reach the page right after `setVisible(true)`. Throws if Windows
+  /// refuses the foreground change.
+  #[napi]
+  pub fn focus(&self) -> Result<()> {
+    let accepted = self.call(|webview| {
+      let hwnd = webview_hwnd(webview);
+      let accepted = window::activate_window(hwnd);
+      // We're on the window's own thread, which SetFocus requires
+      let _ = unsafe { SetFocus(Some(hwnd)) };
+      accepted
+    })?;
+    if !accepted {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "SetForegroundWindow refused the webview window".to_string(),
+      ));
+    }
+    Ok(())
+  }
+
+  /// Hides the window and, given `restoreHwnd` (e.g. from
+  /// `getForegroundWindow` when the overlay was summoned), hands the
+  /// foreground back to it. Throws if that handle is invalid or Windows
+  /// refuses the foreground change.
+  #[napi]
+  pub fn dismiss(&self, restore_hwnd: Option<i64>) -> Result<()> {
+    if let Some(hwnd) = restore_hwnd {
+      window::checked_hwnd(hwnd)?;
+    }
+    // Both steps run on the webview thread: it owns the foreground while the
+    // overlay is active, so Windows lets it pass the foreground on.
+    let id = self.id;
+    let accepted = self.call(move |webview| {
+      set_webview_visible(webview, id, false);
+      restore_hwnd
+        .map(|hwnd| window::activate_window(window::hwnd_from_i64(hwnd)))
+        .unwrap_or(true)
+    })?;
+    if !accepted {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        format!(
+          "SetForegroundWindow refused window {}",
+          restore_hwnd.unwrap_or_default()
+        ),
+      ));
+    }
+    Ok(())
+  }
+
+  /// Whether the window is currently shown. Throws if the webview is gone.
+  #[napi]
+  pub fn is_visible(&self) -> Result<bool> {
+    self.call(|webview| unsafe { IsWindowVisible(webview_hwnd(webview)) }.as_bool())
+  }
+
+  /// Shows the window, then hides it again after `ms` milliseconds, e.g. for a
+  /// HUD notification. Calling it again restarts the countdown; `setVisible`
+  /// cancels it.
+  #[napi]
+  pub fn show_for(&self, ms: u32) -> Result<()> {
+    let generation = self.hide_generation.fetch_add(1, Ordering::SeqCst) + 1;
+    self.dispatch(|webview| webview.set_visible(true))?;
+
+    let store = self.handle.clone();
+    let hide_generation = self.hide_generation.clone();
+    let id = self.id;
+    thread::spawn(move || {
+      thread::sleep(Duration::from_millis(ms as u64));
+      // Superseded by a later `show_for` or `setVisible`
+      if hide_generation.load(Ordering::SeqCst) != generation {
+        return;
+      }
+      dispatch_to(&store, move |webview| {
+        set_webview_visible(webview, id, false)
+      });
+    });
+    Ok(())
+  }
+
+  /// The window's current screen rect, e.g. to save on close and pass back as
+  /// `WebviewOptions.geometry`. Throws if the webview is gone.
+  #[napi]
+  pub fn get_geometry(&self) -> Result<WindowRect> {
+    self
+      .call(|webview| window::window_rect(webview_hwnd(webview)))?
+      .map(WindowRect::from)
+      .ok_or_else(|| {
+        napi::Error::new(
+          napi::Status::GenericFailure,
+          "Failed to read webview geometry".to_string(),
+        )
+      })
+  }
+
+  /// Centers the window in the work area of monitor `monitorIndex`, as
+  /// numbered by `list_monitors`; out-of-range indexes are clamped.
+  #[napi]
+  pub fn center_on_monitor(&self, monitor_index: i32) -> Result<()> {
+    let monitors = list_monitors()?;
+    if monitors.is_empty() {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "No monitors found".to_string(),
+      ));
+    }
+    self.center_in_work_area(&monitors[monitor_index.clamp(0, monitors.len() as i32 - 1) as usize])
+  }
+
+  /// Centers the window in the work area of the monitor `referenceHwnd` is
+  /// on, e.g. the window that was focused when the overlay was summoned.
+  #[napi]
+  pub fn move_to_window_monitor(&self, reference_hwnd: i64) -> Result<()> {
+    let reference = window::checked_hwnd(reference_hwnd)?;
+    let monitor = monitor::window_monitor(reference).ok_or_else(|| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to find the monitor of window {}", reference_hwnd),
+      )
+    })?;
+    self.center_in_work_area(&monitor)
+  }
+
+  /// The native window handle, for the `hwnd` parameters of this module
+  /// (e.g. `register_window_thumbnail`) or other native code. It stays the
+  /// same for the window's life. Throws if the webview is gone.
+  #[napi]
+  pub fn get_hwnd(&self) -> Result<i64> {
+    self.call(|webview| window::hwnd_to_i64(webview_hwnd(webview)))
+  }
+
+  /// Hides the window if it is shown and shows it otherwise, reading the real
+  /// state on the webview thread; returns the new visibility. Cancels a
+  /// pending `show_for` hide, as `set_visible` does.
+  #[napi]
+  pub fn toggle_visible(&self) -> Result<bool> {
+    self.hide_generation.fetch_add(1, Ordering::SeqCst);
+    let id = self.id;
+    self.call(move |webview| {
+      let visible = !unsafe { IsWindowVisible(webview_hwnd(webview)) }.as_bool();
+      set_webview_visible(webview, id, visible);
+      visible
+    })
+  }
+
+  /// Starts a native window drag as if the user had grabbed a title bar, for
+  /// frameless windows with a custom header: call it from the page's
+  /// `mousedown` (relayed through `window.external.invoke`) while the button
+  /// is still held.
+  #[napi]
+  pub fn begin_drag(&self) -> Result<()> {
+    self.dispatch(|webview| unsafe {
+      // The page holds the mouse capture; the caption drag needs it back
+      let _ = ReleaseCapture();
+      SendMessageW(
+        webview_hwnd(webview),
+        WM_NCLBUTTONDOWN,
+        Some(WPARAM(HTCAPTION as usize)),
+        Some(LPARAM(0)),
+      );
+    })
+  }
+
+  /// Confines the mouse cursor to the window's current rect, e.g. while the
+  /// overlay is in a modal selection mode. Call it again after moving or
+  /// resizing the window. The cursor is freed by `release_cursor`, or when
+  /// the window hides or closes. Throws if the window is hidden.
+  #[napi]
+  pub fn clip_cursor_to_window(&self) -> Result<()> {
+    let id = self.id;
+    self
+      .call(move |webview| {
+        let hwnd = webview_hwnd(webview);
+        if !unsafe { IsWindowVisible(hwnd) }.as_bool() {
+          return Err("webview is hidden".to_string());
+        }
+        let rect = window::window_rect(hwnd).ok_or("Failed to read webview geometry")?;
+        unsafe { ClipCursor(Some(&rect)) }.map_err(|e| format!("Failed to clip cursor: {}", e))?;
+        CURSOR_CLIP_OWNER.store(id, Ordering::SeqCst);
+        Ok(())
+      })?
+      .map_err(|message| napi::Error::new(napi::Status::GenericFailure, message))
+  }
+
+  /// Frees the cursor confined by `clip_cursor_to_window`. A no-op if this
+  /// window doesn't hold the clip, so another app's clip is left alone.
+  #[napi]
+  pub fn release_cursor(&self) -> Result<()> {
+    release_cursor_clip(self.id);
+    Ok(())
+  }
+}
+
+/// How `open_webview` interprets its `content` string
+#[napi]
+pub enum ContentKind {
+  Html,
+  Url,
+}
+
+/// Window options for `open_webview`; omitted fields keep the overlay defaults
+#[napi(object, object_to_js = false)]
+#[derive(Default)]
+pub struct WebviewOptions {
+  pub resizable: Option<bool>, // default false
+  pub frameless: Option<bool>, // default true
+  pub debug: Option<bool>,     // default false
+  pub visible: Option<bool>,   // default false
+  /// Initial screen position of the top-left corner; give both or neither
+  /// (default: the OS picks, centered)
+  pub x: Option<i32>,
+  pub y: Option<i32>,
+  /// Exact initial window rect, e.g. saved from `get_geometry`; replaces
+  /// `x`/`y` and the `width`/`height` arguments
+  pub geometry: Option<WindowRect>,
+  /// Make the process per-monitor DPI aware before the first window opens, so
+  /// the overlay is sharp on high-DPI displays (default true). Pass false if
+  /// the host app manages DPI awareness itself.
+  pub dpi_aware: Option<bool>,
+  /// Make it a tool window, kept out of the taskbar and Alt-Tab like a HUD
+  /// should be (default false)
+  pub tool_window: Option<bool>,
+  /// Receives a `WebviewEvent` as the window goes through its lifecycle
+  pub on_event: Option<JsFunction>,
+}
+
+/// Lifecycle notification passed to `WebviewOptions.onEvent`
+#[napi(object)]
+pub struct WebviewEvent {
+  /// `"ready"` once the window is up and the handle usable, `"closed"` when
+  /// it has gone away, or `"error"` if it failed to build or run
+  pub kind: String,
+  /// What went wrong, for `"error"`
+  pub message: Option<String>,
+}
+
+type WebviewEventCallback = ThreadsafeFunction<WebviewEvent, ErrorStrategy::CalleeHandled>;
+
+// Reports a lifecycle event to `onEvent`, if one was given
+fn emit_webview_event(
+  on_event: &Option<WebviewEventCallback>,
+  kind: &str,
+  message: Option<String>,
+) {
+  if let Some(tsfn) = on_event {
+    let event = WebviewEvent {
+      kind: kind.to_string(),
+      message,
+    };
+    tsfn.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+  }
+}
+
+// Page shown when `open_webview` is called without content
+const DEFAULT_HTML: &str = "<h1>Hello world!</h1>";
+
+/// Opens a webview window. `content` is inline HTML by default, or a URL
+/// (remote or `file://`) when `contentKind` is `ContentKind.Url`.
+///
+/// `onMessage` receives every string the page passes to
+/// `window.external.invoke(...)`. The invoke fires on the webview thread; the
+/// callback is queued onto the Node event loop, so it runs asynchronously and
+/// can't return a value to the page — reply with `WebviewHandle.eval` instead.
+///
+/// `options.onEvent` observes the window's lifecycle: `ready`, then `closed`
+/// when it goes away, with an `error` first if building or running failed.
+///
+/// The window is built on its own thread, but the handle is usable at once:
+/// calls made before it's `ready` are queued and run in order once it is.
+#[napi]
+pub fn open_webview(
+  title: String,
+  width: i32,
+  height: i32,
+  content: Option<String>,
+  content_kind: Option<ContentKind>,
+  on_message: Option<JsFunction>,
+  options: Option<WebviewOptions>,
+) -> Result<WebviewHandle> {
+  let content = match (content_kind.unwrap_or(ContentKind::Html), content) {
+    (ContentKind::Url, Some(url)) if !url.trim().is_empty() => Content::Url(url),
+    (ContentKind::Url, _) => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "open_webview needs a non-empty URL".to_string(),
+      ));
+    }
+    (ContentKind::Html, html) => Content::Html(html.unwrap_or_else(|| DEFAULT_HTML.to_string())),
+  };
+  launch_webview(title, width, height, content, on_message, options, None)
+}
+
+/// Opens a webview showing a static web build: `dir` is served over HTTP on
+/// a random loopback port and the window loads `entry` (e.g. `index.html`)
+/// from it, so the page can use `fetch` and module scripts that `file://`
+/// URLs block. The server stops when the window closes. Other arguments are
+/// as for `open_webview`.
+#[napi]
+pub fn open_webview_from_dir(
+  dir: String,
+  entry: String,
+  title: String,
+  width: i32,
+  height: i32,
+  on_message: Option<JsFunction>,
+  options: Option<WebviewOptions>,
+) -> Result<WebviewHandle> {
+  let root = std::path::PathBuf::from(&dir);
+  if !root.is_dir() {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Not a directory: {}", dir),
+    ));
+  }
+  let server = AssetServer::start(root).map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to start asset server: {}", e),
+    )
+  })?;
+  let url = format!(
+    "http://127.0.0.1:{}/{}",
+    server.port(),
+    assets::percent_encode_path(entry.trim_start_matches('/'))
+  );
+  launch_webview(
+    title,
+    width,
+    height,
+    Content::Url(url),
+    on_message,
+    options,
+    Some(server),
+  )
+}
+
+// Builds and runs the window for `open_webview`/`open_webview_from_dir`;
+// `server` lives exactly as long as the window thread does
+fn launch_webview(
+  title: String,
+  width: i32,
+  height: i32,
+  content: Content<String>,
+  on_message: Option<JsFunction>,
+  options: Option<WebviewOptions>,
+  server: Option<AssetServer>,
+) -> Result<WebviewHandle> {
+  let options = options.unwrap_or_default();
+  let resizable = options.resizable.unwrap_or(false);
+  let frameless = options.frameless.unwrap_or(true);
+  let debug = options.debug.unwrap_or(false);
+  let visible = options.visible.unwrap_or(false);
+  let tool_window = options.tool_window.unwrap_or(false);
+  if options.dpi_aware.unwrap_or(true) {
+    // Only affects windows created afterwards, so do it before building
+    monitor::ensure_dpi_awareness();
+  }
+  // Where to move the window once built: a corner, plus an exact size
+  let placement = match (options.geometry, options.x, options.y) {
+    (Some(geometry), None, None) => {
+      if geometry.width <= 0 || geometry.height <= 0 {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          format!(
+            "Invalid webview size: {}x{}",
+            geometry.width, geometry.height
+          ),
+        ));
+      }
+      Some((
+        geometry.x,
+        geometry.y,
+        Some((geometry.width, geometry.height)),
+      ))
+    }
+    (Some(_), _, _) => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "open_webview takes either geometry or x/y, not both".to_string(),
+      ));
+    }
+    (None, Some(x), Some(y)) => Some((x, y, None)),
+    (None, None, None) => None,
+    _ => {
+      return Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "open_webview needs both x and y, or neither".to_string(),
+      ));
+    }
+  };
+  // Windows adjusted after building are shown only then, so they don't jump
+  // or flash a taskbar button
+  let show_later = placement.is_some() || tool_window;
+
+  // Forward page -> Node messages the way `register_hotkey` forwards hotkey events.
+  let on_message: Option<ThreadsafeFunction<String, ErrorStrategy::CalleeHandled>> = on_message
+    .map(|callback| {
+      callback.create_threadsafe_function(
+        0,
+        |ctx: napi::threadsafe_function::ThreadSafeCallContext<String>| Ok(vec![ctx.value]),
+      )
+    })
+    .transpose()?;
+  let on_event: Option<WebviewEventCallback> = options
+    .on_event
+    .map(|callback| {
+      callback.create_threadsafe_function(
+        0,
+        |ctx: napi::threadsafe_function::ThreadSafeCallContext<WebviewEvent>| Ok(vec![ctx.value]),
+      )
+    })
+    .transpose()?;
+
+  let handle_store: SharedHandle = Arc::new(Mutex::new(WebviewState::Starting(Vec::new())));
+  let thread_store = handle_store.clone();
+  let error_store: SharedError = Arc::new(Mutex::new(None));
+  let thread_error = error_store.clone();
+
+  let id = WEBVIEW_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+  WEBVIEWS.lock().unwrap().insert(id, handle_store.clone());
+
+  thread::spawn(move || {
+    let _server = server;
+    let webview = builder()
+      .title(&title)
+      .content(content)
+      .size(width, height)
+      .resizable(resizable)
+      .frameless(frameless)
+      .debug(debug)
+      .user_data(())
+      .invoke_handler(move |_webview, arg| {
+        if let Some(tsfn) = &on_message {
+          tsfn.call(Ok(arg.to_string()), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+        Ok(())
+      })
+      .visible(visible && !show_later)
+      .build();
+    let mut webview = match webview {
+      Ok(webview) => webview,
+      Err(e) => {
+        let message = format!("Failed to build webview: {}", e);
+        log(LogLevel::Error, &message);
+        *thread_error.lock().unwrap() = Some(message.clone());
+        emit_webview_event(&on_event, "error", Some(message));
+        *thread_store.lock().unwrap() = WebviewState::Closed;
+        WEBVIEWS.lock().unwrap().remove(&id);
+        return;
+      }
+    };
+
+    // The builder took the title as a C string; set it again as UTF-16
+    set_window_title(webview_hwnd(&webview), &title);
+
+    if let Some((x, y, size)) = placement {
+      let (width, height) = size.unwrap_or_default();
+      let keep_size = if size.is_none() {
+        SWP_NOSIZE
+      } else {
+        SET_WINDOW_POS_FLAGS(0)
+      };
+      let _ = unsafe {
+        SetWindowPos(
+          webview_hwnd(&webview),
+          None,
+          x,
+          y,
+          width,
+          height,
+          keep_size | SWP_NOZORDER | SWP_NOACTIVATE,
+        )
+      };
+    }
+
+    if tool_window {
+      update_ex_style(webview_hwnd(&webview), WS_EX_TOOLWINDOW, WS_EX_APPWINDOW);
+    }
+    if visible && show_later {
+      webview.set_visible(true);
+    }
+
+    // Go live, replaying calls made while building in the order they came
+    let handle = webview.handle();
+    let exit_requested = {
+      let mut state = thread_store.lock().unwrap();
+      match std::mem::replace(&mut *state, WebviewState::Running(handle.clone())) {
+        WebviewState::Starting(pending) => {
+          for f in pending {
+            let _ = handle.dispatch(move |webview| {
+              f(webview);
+              Ok(())
+            });
+          }
+          false
+        }
+        _ => {
+          *state = WebviewState::Closed;
+          true
+        }
+      }
+    };
+    if exit_requested {
+      WEBVIEWS.lock().unwrap().remove(&id);
+      emit_webview_event(&on_event, "closed", None);
+      return;
+    }
+    emit_webview_event(&on_event, "ready", None);
+
+    if let Err(e) = webview.run() {
+      let message = format!("Webview exited with an error: {}", e);
+      log(LogLevel::Error, &message);
+      *thread_error.lock().unwrap() = Some(message.clone());
+      emit_webview_event(&on_event, "error", Some(message));
+    }
+    *thread_store.lock().unwrap() = WebviewState::Closed;
+    WEBVIEWS.lock().unwrap().remove(&id);
+    release_cursor_clip(id);
+    emit_webview_event(&on_event, "closed", None);
+  });
+
+  Ok(WebviewHandle {
+    id,
+    handle: handle_store,
+    error: error_store,
+    hide_generation: Arc::new(AtomicU32::new(0)),
+  })
+}
+
+/// Closes every window opened by `open_webview`, including ones whose
+/// handle JS has dropped. Their `onEvent` callbacks still see `closed`.
+#[napi]
+pub fn close_all_webviews() -> Result<()> {
+  let stores: Vec<SharedHandle> = WEBVIEWS
+    .lock()
+    .unwrap()
+    .drain()
+    .map(|(_, store)| store)
+    .collect();
+  for store in &stores {
+    exit_webview(store);
+  }
+  Ok(())
+}
+
+/// Which key transitions `register_key_release` reports
+#[napi]
+pub enum KeyEventKind {
+  Up,   // releases only (the default)
+  Down, // presses, including auto-repeat while held
+  Both,
+}
+
+/// Payload passed to a key hook callback, copied from KBDLLHOOKSTRUCT
+#[napi(object)]
+pub struct KeyEvent {
+  pub vk_code: u32,   // side-specific, e.g. 164 for left Alt
+  pub scan_code: u32, // hardware scan code
+  pub time: u32,      // message timestamp in ms (GetTickCount clock)
+  /// Whether every modifier in `KeyHookOptions.modifiers` was held (true if none)
+  pub chord_held: bool,
+  /// Whether this was a press rather than a release
+  pub is_down: bool,
+}
+
+type KeyCallback = ThreadsafeFunction<KeyEvent, ErrorStrategy::CalleeHandled>;
+type CaptureCallback = ThreadsafeFunction<HotkeyCapture, ErrorStrategy::CalleeHandled>;
+type KeyDeferred = JsDeferred<KeyEvent, Box<dyn FnOnce(Env) -> Result<KeyEvent> + Send>>;
+
+// Where the key hook delivers its events
+enum KeySink {
+  Callback(KeyCallback),
+  Promise(KeyDeferred),     // from `wait_for_key_release`, always one-shot
+  Capture(CaptureCallback), // from `start_hotkey_capture`
+  Hold(KeyCallback),        // from `register_hold_hotkey`, always repeating
+  DoubleTap(KeyCallback, DoubleTap), // from `register_double_tap`, always repeating
+}
+
+// What a `register_double_tap` hook remembers between events
+struct DoubleTap {
+  window_ms: u32,        // Longest gap between the two presses
+  last_tap: Option<u32>, // Time of a first press still waiting for its second
+  held: bool,            // The key is down, so further presses are auto-repeat
+}
+
+impl KeySink {
+  // Hands over the last event a one-shot hook will produce; capture sinks
+  // get a `HotkeyCapture` from `capture_key` instead
+  fn settle(self, event: KeyEvent) {
+    match self {
+      KeySink::Callback(tsfn) | KeySink::Hold(tsfn) | KeySink::DoubleTap(tsfn, _) => {
+        let _ = tsfn.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+      KeySink::Promise(deferred) => deferred.resolve(Box::new(move |_| Ok(event))),
+      KeySink::Capture(_) => {}
+    }
+  }
+
+  // Reports that the hook couldn't be installed
+  fn fail(self, error: napi::Error) {
+    match self {
+      KeySink::Callback(tsfn) | KeySink::Hold(tsfn) | KeySink::DoubleTap(tsfn, _) => {
+        let _ = tsfn.call(Err(error), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+      KeySink::Promise(deferred) => deferred.reject(error),
+      KeySink::Capture(tsfn) => {
+        let _ = tsfn.call(Err(error), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    }
+  }
+
+  // Drops the sink without an event; a pending promise must still settle,
+  // or it would keep the Node event loop alive
+  fn cancel(self) {
+    if let KeySink::Promise(deferred) = self {
+      deferred.reject(napi::Error::new(
+        napi::Status::Cancelled,
+        "Key hook was removed before the key was released".to_string(),
+      ));
+    }
+  }
+}
+
+// The module (this addon's DLL) that low-level hooks are installed from.
+// GetModuleHandleW(NULL) would give node.exe instead, and can fail outright.
+fn hook_module() -> WinResult<HINSTANCE> {
+  let mut module = HMODULE::default();
+  unsafe {
+    GetModuleHandleExW(
+      GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+      PCWSTR(hook_module as *const () as *const u16),
+      &mut module,
+    )
+  }?;
+  Ok(HINSTANCE(module.0))
+}
+
+#[derive(Copy, Clone)]
+struct SafeHhook(HHOOK);
+unsafe impl Send for SafeHhook {}
+unsafe impl Sync for SafeHhook {}
+
+// One installed low-level keyboard hook and what it is waiting for
+struct KeyHook {
+  watch: KeyWatch,
+  sink: Option<KeySink>,  // `None` once a one-shot hook has delivered
+  thread_id: Option<u32>, // Set once the hook thread is running
+  hook: Option<SafeHhook>,
+}
+
+// What a hook watches for, kept apart from its sink so `watch_key` can
+// decide without a JS environment
+struct KeyWatch {
+  vk: u32,          // Virtual key the hook watches
+  repeat: bool,     // Stays installed after the first event
+  suppress: bool,   // Matching events are swallowed instead of passed on
+  modifiers: u32,   // MOD_* bits that must be held for an event to count
+  transitions: u32, // Which are reported: bit 0 presses, bit 1 releases
+  hold: bool,       // A `Hold` sink: releases count only after the full combo went down
+  hold_armed: bool, // `hold`: the full combo went down since the last release
+}
+
+// Every installed key hook, keyed by the ID `register_key_release` returns
+static KEY_HOOKS: Lazy<Mutex<HashMap<u32, KeyHook>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_KEY_HOOK_ID: AtomicU32 = AtomicU32::new(1);
+
+thread_local! {
+  // The `KEY_HOOKS` entry this hook thread serves. A low-level hook runs on
+  // the thread that installed it and gets no context pointer, so this is
+  // how `keyboard_proc` tells the hooks apart.
+  static CURRENT_KEY_HOOK: Cell<u32> = const { Cell::new(0) };
+}
+
+// Whether all modifiers in `mask` are down right now, either side counting
+fn modifiers_held(mask: u32) -> bool {
+  held_modifiers() & mask == mask
+}
+
+// The MOD_* bits of every modifier down right now, either side counting
+fn held_modifiers() -> u32 {
+  let down = |vk: VIRTUAL_KEY| unsafe { GetAsyncKeyState(vk.0 as i32) } < 0;
+  [
+    (0x0001, down(VK_MENU)),                  // MOD_ALT
+    (0x0002, down(VK_CONTROL)),               // MOD_CONTROL
+    (0x0004, down(VK_SHIFT)),                 // MOD_SHIFT
+    (0x0008, down(VK_LWIN) || down(VK_RWIN)), // MOD_WIN
+  ]
+  .into_iter()
+  .filter(|(_, held)| *held)
+  .fold(0, |mask, (bit, _)| mask | bit)
+}
+
+// Shift, Control, Alt (generic and sided) and the Windows keys
+fn is_modifier_vk(vk: u32) -> bool {
+  matches!(vk, 0x10..=0x12 | 0xA0..=0xA5 | 0x5B | 0x5C)
+}
+
+/// Whether a key reported by the low-level hook counts as the watched key.
+/// The hook always reports the physical, side-specific code, so the generic
+/// modifier codes also match both of their sides:
+///
+/// | watched            | matches                                   |
+/// |--------------------|-------------------------------------------|
+/// | `VK_MENU` (18)     | `VK_LMENU` (164, left Alt), `VK_RMENU` (165, right Alt / AltGr) |
+/// | `VK_CONTROL` (17)  | `VK_LCONTROL` (162), `VK_RCONTROL` (163)  |
+/// | `VK_SHIFT` (16)    | `VK_LSHIFT` (160), `VK_RSHIFT` (161)      |
+/// | anything else      | only itself                               |
+fn vk_matches(watched: u32, actual: u32) -> bool {
+  match watched {
+    0x12 => matches!(actual, 0x12 | 0xA4 | 0xA5), // VK_MENU
+    0x11 => matches!(actual, 0x11 | 0xA2 | 0xA3), // VK_CONTROL
+    0x10 => matches!(actual, 0x10 | 0xA0 | 0xA1), // VK_SHIFT
+    _ => watched == actual,
+  }
+}
+
+// `vk_matches` for a hook event, so a watched numpad key matches whatever
+// the NumLock state
+fn key_matches(watched: u32, kb: &KBDLLHOOKSTRUCT) -> bool {
+  let extended = kb.flags.contains(LLKHF_EXTENDED);
+  vk_matches(watched, kb.vkCode) || keys::numpad_key(kb.vkCode, extended) == Some(watched)
+}
+
+// Removes hook `id` from the registry, unhooking it right here in case its
+// thread is never scheduled again, and tells that thread to exit
+fn take_key_hook(hooks: &mut HashMap<u32, KeyHook>, id: u32) -> Option<KeyHook> {
+  let mut hook = hooks.remove(&id)?;
+  if let Some(SafeHhook(h)) = hook.hook.take() {
+    let _ = unsafe { UnhookWindowsHookEx(h) };
+  }
+  if let Some(tid) = hook.thread_id.take() {
+    let _ = unsafe { PostThreadMessageW(tid, WM_QUIT, WPARAM(0), LPARAM(0)) };
+  }
+  Some(hook)
+}
+
+// Removes key hook `id`, or every key hook, settling whatever their sinks
+// still owe JS and stopping the hotkey half of any hold pair among them
+fn remove_key_hooks(id: Option<u32>) {
+  let removed: Vec<(u32, KeyHook)> = {
+    let mut hooks = KEY_HOOKS.lock().unwrap();
+    let ids: Vec<u32> = match id {
+      Some(id) => vec![id],
+      None => hooks.keys().copied().collect(),
+    };
+    ids
+      .into_iter()
+      .filter_map(|id| take_key_hook(&mut hooks, id).map(|hook| (id, hook)))
+      .collect()
+  };
+  for (id, hook) in removed {
+    if let Some(sink) = hook.sink {
+      sink.cancel();
+    }
+    unpair_hold(id);
+  }
+}
+
+// What `keyboard_proc` does with an event once a hook has looked at it
+#[derive(Debug, PartialEq)]
+enum KeyOutcome {
+  Pass,                   // Let it through
+  Swallow,                // Stop it here: no later hook or app sees it
+  Done { swallow: bool }, // The hook delivered its last event; remove it
+}
+
+// What `keyboard_proc` owes JS for an event, handed over only once the
+// registry lock is released
+enum KeyDelivery {
+  Settle(KeySink, KeyEvent),               // A one-shot sink's only event
+  Call(KeyCallback, KeyEvent),             // A clone of a repeating callback
+  Capture(CaptureCallback, HotkeyCapture), // The end of a hotkey capture
+}
+
+impl KeyDelivery {
+  // Calls from one hook thread are queued in order and the queue is
+  // unbounded, so back-to-back events all reach JS, in sequence
+  fn deliver(self) {
+    match self {
+      KeyDelivery::Settle(sink, event) => sink.settle(event),
+      KeyDelivery::Call(tsfn, event) => {
+        let _ = tsfn.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+      KeyDelivery::Capture(tsfn, capture) => {
+        let _ = tsfn.call(Ok(capture), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    }
+  }
+}
+
+// A repeating callback to call outside the lock. Cloning one whose env is
+// already gone panics, so those are skipped.
+fn callback_clone(tsfn: &KeyCallback) -> Option<KeyCallback> {
+  (!tsfn.aborted()).then(|| tsfn.clone())
+}
+
+fn key_event(is_down: bool, kb: &KBDLLHOOKSTRUCT, chord_held: bool) -> KeyEvent {
+  KeyEvent {
+    vk_code: kb.vkCode,
+    scan_code: kb.scanCode,
+    time: kb.time,
+    chord_held,
+    is_down,
+  }
+}
+
+// `start_hotkey_capture`'s half of `keyboard_proc`: the first non-modifier
+// press ends the capture and is swallowed, so it doesn't also reach the app
+fn capture_key(is_down: bool, kb: &KBDLLHOOKSTRUCT) -> Option<HotkeyCapture> {
+  (is_down && !is_modifier_vk(kb.vkCode)).then(|| HotkeyCapture {
+    modifiers: held_modifiers(),
+    vk: kb.vkCode,
+  })
+}
+
+// The key-release hooks' half of `keyboard_proc`: what to do with the event
+// and what, if anything, to report. `chord_held` says whether
+// `watch.modifiers` are down right now, `suspended` whether
+// `suspend_hotkeys` is in effect.
+fn watch_key(
+  watch: &mut KeyWatch,
+  is_down: bool,
+  kb: &KBDLLHOOKSTRUCT,
+  chord_held: bool,
+  suspended: bool,
+) -> (KeyOutcome, Option<KeyEvent>) {
+  if !key_matches(watch.vk, kb) {
+    return (KeyOutcome::Pass, None);
+  }
+  if watch.hold && is_down && !suspended && chord_held {
+    watch.hold_armed = true;
+  }
+  let transition_bit = if is_down { 0b01 } else { 0b10 };
+  if watch.transitions & transition_bit == 0 {
+    return (KeyOutcome::Pass, None);
+  }
+  if watch.hold && !std::mem::take(&mut watch.hold_armed) {
+    // released without the combo having been pressed first
+    return (KeyOutcome::Pass, None);
+  }
+  if !chord_held && !watch.repeat {
+    // a one-shot hook keeps waiting for the full chord
+    return (KeyOutcome::Pass, None);
+  }
+  let swallow = chord_held && watch.suppress;
+  let outcome = if !watch.repeat {
+    KeyOutcome::Done { swallow }
+  } else if swallow {
+    KeyOutcome::Swallow
+  } else {
+    KeyOutcome::Pass // stays armed until it is unregistered
+  };
+  (outcome, Some(key_event(is_down, kb, chord_held)))
+}
+
+// `register_double_tap`'s half of `keyboard_proc`: the press that completes
+// a double tap, if this is one. Presses of any other key in between start
+// over, so e.g. Shift+A Shift doesn't count.
+fn tap_key(
+  tap: &mut DoubleTap,
+  vk: u32,
+  is_down: bool,
+  kb: &KBDLLHOOKSTRUCT,
+  suspended: bool,
+) -> Option<KeyEvent> {
+  if !key_matches(vk, kb) {
+    if is_down {
+      tap.last_tap = None;
+    }
+    return None;
+  }
+  if !is_down {
+    tap.held = false;
+    return None;
+  }
+  if std::mem::replace(&mut tap.held, true) || suspended {
+    return None;
+  }
+  // `time` wraps with GetTickCount, about every 49.7 days
+  match tap.last_tap.take() {
+    Some(first) if kb.time.wrapping_sub(first) <= tap.window_ms => {
+      Some(key_event(is_down, kb, true))
+    }
+    _ => {
+      tap.last_tap = Some(kb.time);
+      None
+    }
+  }
+}
+
+// Runs the half of `keyboard_proc` that fits `hook`'s sink
+fn dispatch_key(
+  hook: &mut KeyHook,
+  is_down: bool,
+  kb: &KBDLLHOOKSTRUCT,
+) -> (KeyOutcome, Option<KeyDelivery>) {
+  let suspended = HOTKEYS_SUSPENDED.load(Ordering::SeqCst);
+  match &mut hook.sink {
+    Some(KeySink::Capture(_)) => match capture_key(is_down, kb) {
+      Some(capture) => {
+        let delivery = match hook.sink.take() {
+          Some(KeySink::Capture(tsfn)) => Some(KeyDelivery::Capture(tsfn, capture)),
+          _ => None,
+        };
+        (KeyOutcome::Done { swallow: true }, delivery)
+      }
+      None => (KeyOutcome::Pass, None),
+    },
+    Some(KeySink::DoubleTap(tsfn, tap)) => {
+      let delivery = tap_key(tap, hook.watch.vk, is_down, kb, suspended)
+        .and_then(|event| Some(KeyDelivery::Call(callback_clone(tsfn)?, event)));
+      (KeyOutcome::Pass, delivery)
+    }
+    Some(_) => {
+      let chord_held = modifiers_held(hook.watch.modifiers);
+      let (outcome, event) = watch_key(&mut hook.watch, is_down, kb, chord_held, suspended);
+      let delivery = event.and_then(|event| match (&outcome, &hook.sink) {
+        (KeyOutcome::Done { .. }, _) => Some(KeyDelivery::Settle(hook.sink.take()?, event)),
+        (_, Some(KeySink::Callback(tsfn) | KeySink::Hold(tsfn))) => {
+          Some(KeyDelivery::Call(callback_clone(tsfn)?, event))
+        }
+        _ => None,
+      });
+      (outcome, delivery)
+    }
+    None => (KeyOutcome::Pass, None),
+  }
+}
+
+extern "system" fn keyboard_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+  // Keys pressed while Alt is down arrive as WM_SYS* messages
+  let is_down = match wparam.0 as u32 {
+    WM_KEYDOWN | WM_SYSKEYDOWN => Some(true),
+    WM_KEYUP | WM_SYSKEYUP => Some(false),
+    _ => None,
+  };
+  if code == HC_ACTION as i32
+    && let Some(is_down) = is_down
+  {
+    let kb = unsafe { *(lparam.0 as *const KBDLLHOOKSTRUCT) };
+    let id = CURRENT_KEY_HOOK.get();
+    let (swallow, delivery) = {
+      let mut hooks = KEY_HOOKS.lock().unwrap();
+      let (outcome, delivery) = match hooks.get_mut(&id) {
+        Some(hook) => dispatch_key(hook, is_down, &kb),
+        None => (KeyOutcome::Pass, None),
+      };
+      let swallow = match outcome {
+        KeyOutcome::Pass => false,
+        KeyOutcome::Swallow => true,
+        KeyOutcome::Done { swallow } => {
+          take_key_hook(&mut hooks, id);
+          swallow
+        }
+      };
+      (swallow, delivery)
+    };
+    if let Some(delivery) = delivery {
+      delivery.deliver();
+    }
+    if swallow {
+      // nonzero stops the event here: no later hook or app sees it
+      return LRESULT(1);
+    }
+  }
+  unsafe { CallNextHookEx(None, code, wparam, lparam) }
+}
+
+/// Options for `register_key_release`
+#[napi(object)]
+#[derive(Default)]
+pub struct KeyHookOptions {
+  /// Keep firing on every release until `unregister_key_release` (default false: one-shot)
+  pub repeat: Option<bool>,
+  /// Swallow the watched key's release so no app sees it, e.g. so releasing
+  /// Alt doesn't open the foreground app's menu (default false). This is
+  /// global: apps that saw the key go down never see it come up, so keep
+  /// the hook's lifetime short.
+  pub suppress: Option<bool>,
+  /// Modifiers that must be held with the key, e.g. `[Modifiers.Alt]` to
+  /// catch Tab only while Alt is down. A one-shot hook ignores releases
+  /// without them and `suppress` lets those through; in repeat mode every
+  /// release fires and `chordHeld` tells them apart.
+  pub modifiers: Option<Either<u32, Vec<Modifiers>>>,
+  /// Report presses, releases or both (default `KeyEventKind.Up`); `repeat`,
+  /// `suppress` and `modifiers` then apply to every reported event
+  pub kind: Option<KeyEventKind>,
+}
+
+/// Calls `callback` the next time key `vk` is released, then unhooks; with
+/// `repeat` it fires on every release until `unregister_key_release`.
+/// `kind` switches to presses, or both, e.g. to step through a list with Tab
+/// while Alt is held.
+/// Generic modifiers (`VK_MENU`, `VK_CONTROL`, `VK_SHIFT`) match either side.
+/// Each call installs its own hook, so several can watch at once; the
+/// returned ID removes just this one via `unregister_key_release`.
+/// If the hook can't be installed, the callback receives the error instead.
+#[napi]
+pub fn register_key_release(
+  mut env: Env,
+  vk: u32,
+  callback: JsFunction,
+  options: Option<KeyHookOptions>,
+) -> Result<u32> {
+  install_key_hook(&mut env, vk, options.unwrap_or_default(), || {
+    let tsfn = callback.create_threadsafe_function(
+      0,
+      |ctx: napi::threadsafe_function::ThreadSafeCallContext<KeyEvent>| Ok(vec![ctx.value]),
+    )?;
+    Ok(KeySink::Callback(tsfn))
+  })
+}
+
+/// Resolves with the `KeyEvent` the next time key `vk` is released, e.g.
+/// the Alt release that commits an Alt+Tab style switch, then unhooks.
+/// `modifiers` and `suppress` work as for `register_key_release`; `repeat`
+/// and `kind` are ignored. Rejects with `Cancelled` if no release comes
+/// within `timeoutMs`, which removes just this hook, or if
+/// `unregister_key_release()` (or `shutdown`) removes it first; rejects
+/// with the error if the hook can't be installed.
+#[napi(ts_return_type = "Promise<KeyEvent>")]
+pub fn wait_for_key_release(
+  mut env: Env,
+  vk: u32,
+  options: Option<KeyHookOptions>,
+  timeout_ms: Option<u32>,
+) -> Result<JsObject> {
+  let options = KeyHookOptions {
+    repeat: Some(false),
+    kind: Some(KeyEventKind::Up),
+    ..options.unwrap_or_default()
+  };
+  let mut promise = None;
+  let deferred_env = env;
+  let id = install_key_hook(&mut env, vk, options, || {
+    let (deferred, js_promise) = deferred_env.create_deferred()?;
+    promise = Some(js_promise);
+    Ok(KeySink::Promise(deferred))
+  })?;
+  if let Some(ms) = timeout_ms {
+    thread::spawn(move || {
+      thread::sleep(Duration::from_millis(ms as u64));
+      // Gone already if the key was released or the hook removed
+      let hook = take_key_hook(&mut KEY_HOOKS.lock().unwrap(), id);
+      if let Some(KeySink::Promise(deferred)) = hook.and_then(|hook| hook.sink) {
+        deferred.reject(napi::Error::new(
+          napi::Status::Cancelled,
+          format!("Key {} was not released within {} ms", vk, ms),
+        ));
+      }
+    });
+  }
+  Ok(promise.expect("install_key_hook creates the sink on success"))
+}
+
+// Installs a low-level keyboard hook on its own thread and returns its ID.
+// `sink` is only created once the options check out, so nothing leaks on error.
+fn install_key_hook(
+  env: &mut Env,
+  vk: u32,
+  options: KeyHookOptions,
+  sink: impl FnOnce() -> Result<KeySink>,
+) -> Result<u32> {
+  let modifiers = options.modifiers.map(resolve_modifiers).transpose()?;
+  ensure_cleanup_hook(env)?;
+
+  let sink = sink()?;
+  let transitions = match options.kind.unwrap_or(KeyEventKind::Up) {
+    KeyEventKind::Up => 0b10,
+    KeyEventKind::Down => 0b01,
+    KeyEventKind::Both => 0b11,
+  };
+  let id = NEXT_KEY_HOOK_ID.fetch_add(1, Ordering::SeqCst);
+  KEY_HOOKS.lock().unwrap().insert(
+    id,
+    KeyHook {
+      watch: KeyWatch {
+        vk,
+        repeat: options.repeat.unwrap_or(false),
+        suppress: options.suppress.unwrap_or(false),
+        modifiers: modifiers.unwrap_or(0),
+        transitions,
+        hold: matches!(sink, KeySink::Hold(_)),
+        hold_armed: false,
+      },
+      sink: Some(sink),
+      thread_id: None,
+      hook: None,
+    },
+  );
+
+  thread::spawn(move || unsafe {
+    CURRENT_KEY_HOOK.set(id);
+    // Make sure the thread has a message queue before its ID is published,
+    // or a WM_QUIT posted right away would be lost
+    let mut msg = MSG::default();
+    let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+    let installed = {
+      let mut hooks = KEY_HOOKS.lock().unwrap();
+      let Some(entry) = hooks.get_mut(&id) else {
+        return; // unregistered before this thread got going
+      };
+      let hook = hook_module()
+        .and_then(|module| SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_proc), Some(module), 0));
+      match hook {
+        Ok(hook) => {
+          entry.hook = Some(SafeHhook(hook));
+          entry.thread_id = Some(GetCurrentThreadId());
+          Ok(())
+        }
+        Err(e) => Err((e, hooks.remove(&id))),
+      }
+    };
+    if let Err((e, entry)) = installed {
+      log(
+        LogLevel::Error,
+        format!("SetWindowsHookExW failed: {:?}", e),
+      );
+      // hand the failure to the callback's error channel; it never fires otherwise
+      if let Some(sink) = entry.and_then(|entry| entry.sink) {
+        sink.fail(napi::Error::new(
+          napi::Status::GenericFailure,
+          format!("Failed to install keyboard hook: {}", e),
+        ));
+      }
+      // the hotkey half of a hold pair makes no sense on its own
+      unpair_hold(id);
+      return;
+    }
+
+    // WM_QUIT comes once the hook is removed: by a one-shot fire or
+    // `unregister_key_release`
+    run_message_loop("keyboard hook", |_| Pumped::Dispatch);
+
+    // Still registered only if GetMessageW failed
+    remove_key_hooks(Some(id));
+  });
+
+  Ok(id)
+}
+
+/// Removes the key hook `id` returned by `register_key_release` (or
+/// `register_alt_release`), or every key hook when `id` is omitted. A no-op
+/// for hooks that are already gone.
+#[napi]
+pub fn unregister_key_release(id: Option<u32>) -> Result<()> {
+  remove_key_hooks(id);
+  Ok(())
+}
+
+/// Shorthand for `register_key_release` on the Alt key; left or right Alt fires it.
+#[napi]
+pub fn register_alt_release(
+  env: Env,
+  callback: JsFunction,
+  options: Option<KeyHookOptions>,
+) -> Result<u32> {
+  register_key_release(env, VK_MENU.0 as u32, callback, options)
+}
+
+/// Registers a hotkey whose press calls `onPress` (with a `HotkeyEvent`) and
+/// whose release of `vk` then calls `onRelease` (with a `KeyEvent`), e.g.
+/// hold to preview, release to dismiss. The release counts however the
+/// modifiers were let go. `HotkeyHandle.unregister` and
+/// `unregister_key_release()` each remove both halves.
+#[napi]
+pub fn register_hold_hotkey(
+  mut env: Env,
+  modifiers: Either<u32, Vec<Modifiers>>,
+  vk: u32,
+  on_press: JsFunction,
+  on_release: JsFunction,
+) -> Result<HotkeyHandle> {
+  let mask = resolve_modifiers(modifiers)?;
+  let options = KeyHookOptions {
+    repeat: Some(true),
+    modifiers: Some(Either::A(mask)),
+    ..Default::default()
+  };
+  let hook_id = install_key_hook(&mut env, vk, options, || {
+    Ok(KeySink::Hold(on_release.create_threadsafe_function(
+      0,
+      |ctx: napi::threadsafe_function::ThreadSafeCallContext<KeyEvent>| Ok(vec![ctx.value]),
+    )?))
+  })?;
+  let options = HotkeyOptions {
+    modifiers: Either::A(mask),
+    vk,
+    id: None,
+    no_repeat: None,
+    debounce_ms: None,
+    retry: None,
+    max_call_failures: None,
+    scope_hwnd: None,
+    on_heartbeat: None,
+    heartbeat_ms: None,
+    verify_ms: None,
+    on_lost: None,
+    on_restored: None,
+  };
+  match register_hotkey_ex(env, options, on_press) {
+    Ok(handle) => {
+      HOLD_PAIRS
+        .lock()
+        .unwrap()
+        .insert(hook_id, handle.control.clone());
+      // The hook thread may have failed to install before the pair existed
+      if !KEY_HOOKS.lock().unwrap().contains_key(&hook_id) {
+        let _ = stop_listener(&handle.control);
+      }
+      Ok(handle)
+    }
+    Err(e) => {
+      remove_key_hooks(Some(hook_id));
+      Err(e)
+    }
+  }
+}
+
+/// Calls `callback` (with the second press's `KeyEvent`) whenever key `vk`
+/// is pressed twice within `window_ms`, e.g. double-tap Shift: gestures
+/// `register_hotkey` can't express. Pressing any other key in between, or
+/// holding `vk` down, doesn't count; generic modifiers match either side.
+/// Respects `suspend_hotkeys`. Returns a hook ID for `unregister_key_release`.
+#[napi]
+pub fn register_double_tap(
+  mut env: Env,
+  vk: u32,
+  window_ms: u32,
+  callback: JsFunction,
+) -> Result<u32> {
+  // `tap_key` sees both transitions regardless of the options
+  install_key_hook(&mut env, vk, KeyHookOptions::default(), || {
+    let tsfn = callback.create_threadsafe_function(
+      0,
+      |ctx: napi::threadsafe_function::ThreadSafeCallContext<KeyEvent>| Ok(vec![ctx.value]),
+    )?;
+    let tap = DoubleTap {
+      window_ms,
+      last_tap: None,
+      held: false,
+    };
+    Ok(KeySink::DoubleTap(tsfn, tap))
+  })
+}
+
+/// A combo recorded by `start_hotkey_capture`, ready for `register_hotkey`
+#[napi(object)]
+pub struct HotkeyCapture {
+  pub modifiers: u32, // MOD_* bits held when the key went down
+  pub vk: u32,
+}
+
+/// Records the next combo the user presses, for a "press the keys you want"
+/// settings field: on the first non-modifier key press, `callback` receives
+/// `{ modifiers, vk }` and the hook is removed. That press is swallowed.
+/// If the hook can't be installed, the callback receives the error instead.
+#[napi]
+pub fn start_hotkey_capture(mut env: Env, callback: JsFunction) -> Result<()> {
+  install_key_hook(&mut env, 0, KeyHookOptions::default(), || {
+    let tsfn = callback.create_threadsafe_function(
+      0,
+      |ctx: napi::threadsafe_function::ThreadSafeCallContext<HotkeyCapture>| Ok(vec![ctx.value]),
+    )?;
+    Ok(KeySink::Capture(tsfn))
+  })?;
+  Ok(())
+}
+
+/// Stops every running `start_hotkey_capture` without reporting a combo. A
+/// no-op if no capture is running.
+#[napi]
+pub fn cancel_hotkey_capture() -> Result<()> {
+  let captures: Vec<u32> = KEY_HOOKS
+    .lock()
+    .unwrap()
+    .iter()
+    .filter(|(_, hook)| matches!(hook.sink, Some(KeySink::Capture(_))))
+    .map(|(id, _)| *id)
+    .collect();
+  for id in captures {
+    remove_key_hooks(Some(id));
+  }
+  Ok(())
+}
+
+/// Whether key `vk` is held down right now. A cheap point-in-time check, e.g.
+/// to tell a held modifier from a tap after a hotkey fires.
+#[napi]
+pub fn is_key_down(vk: u32) -> bool {
+  // The high bit of the result is set while the key is down
+  let state = unsafe { GetAsyncKeyState(vk as i32) };
+  state < 0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn key(vk: u32, time: u32) -> KBDLLHOOKSTRUCT {
+    KBDLLHOOKSTRUCT {
+      vkCode: vk,
+      time,
+      ..Default::default()
+    }
+  }
+
+  // Watches Alt releases, like `register_alt_release`
+  fn alt_watch(repeat: bool) -> KeyWatch {
+    KeyWatch {
+      vk: 0x12,
+      repeat,
+      suppress: false,
+      modifiers: 0,
+      transitions: 0b10,
+      hold: false,
+      hold_armed: false,
+    }
+  }
+
+  #[test]
+  fn repeating_hook_reports_back_to_back_releases() {
+    let mut watch = alt_watch(true);
+    let times: Vec<u32> = [100, 101]
+      .into_iter()
+      .map(|time| {
+        let (outcome, event) = watch_key(&mut watch, false, &key(0xA4, time), true, false);
+        assert_eq!(outcome, KeyOutcome::Pass);
+        event.expect("every release is reported").time
+      })
+      .collect();
+    assert_eq!(times, [100, 101]);
+  }
+
+  #[test]
+  fn one_shot_hook_finishes_on_the_first_full_chord() {
+    let mut watch = alt_watch(false);
+    let (outcome, event) = watch_key(&mut watch, false, &key(0xA5, 100), false, false);
+    assert_eq!(outcome, KeyOutcome::Pass);
+    assert!(event.is_none());
+    let (outcome, event) = watch_key(&mut watch, false, &key(0xA5, 200), true, false);
+    assert_eq!(outcome, KeyOutcome::Done { swallow: false });
+    assert!(event.is_some_and(|event| event.vk_code == 0xA5 && !event.is_down));
+  }
+
+  #[test]
+  fn release_hook_ignores_presses_and_other_keys() {
+    let mut watch = alt_watch(true);
+    assert!(
+      watch_key(&mut watch, true, &key(0xA4, 100), true, false)
+        .1
+        .is_none()
+    );
+    assert!(
+      watch_key(&mut watch, false, &key(0x41, 100), true, false)
+        .1
+        .is_none()
+    );
+  }
+
+  #[test]
+  fn hold_release_counts_only_after_the_combo_went_down() {
+    let mut watch = KeyWatch {
+      vk: 0x41,
+      hold: true,
+      ..alt_watch(true)
+    };
+    assert!(
+      watch_key(&mut watch, false, &key(0x41, 100), true, false)
+        .1
+        .is_none()
+    );
+    // Pressed while hotkeys are suspended: not armed
+    watch_key(&mut watch, true, &key(0x41, 200), true, true);
+    assert!(
+      watch_key(&mut watch, false, &key(0x41, 300), true, false)
+        .1
+        .is_none()
+    );
+    watch_key(&mut watch, true, &key(0x41, 400), true, false);
+    assert!(
+      watch_key(&mut watch, false, &key(0x41, 500), false, false)
+        .1
+        .is_some()
+    );
+  }
+
+  #[test]
+  fn numpad_hook_matches_with_numlock_off() {
+    let mut watch = KeyWatch {
+      vk: 0x65, // Numpad5
+      ..alt_watch(true)
+    };
+    let clear = key(0x0C, 100);
+    assert!(
+      watch_key(&mut watch, false, &clear, true, false)
+        .1
+        .is_some()
+    );
+    let mut end = key(0x23, 100);
+    end.flags = LLKHF_EXTENDED;
+    watch.vk = 0x61; // Numpad1, not the dedicated End key
+    assert!(watch_key(&mut watch, false, &end, true, false).1.is_none());
+  }
+
+  #[test]
+  fn double_tap_needs_two_presses_in_the_window() {
+    let mut tap = DoubleTap {
+      window_ms: 300,
+      last_tap: None,
+      held: false,
+    };
+    let mut step = |is_down, vk, time| tap_key(&mut tap, 0x10, is_down, &key(vk, time), false);
+    assert!(step(true, 0xA0, 0).is_none());
+    // Auto-repeat while held doesn't count as a second press
+    assert!(step(true, 0xA0, 30).is_none());
+    assert!(step(false, 0xA0, 60).is_none());
+    assert!(step(true, 0xA0, 200).is_some());
+    // Too slow
+    assert!(step(false, 0xA0, 210).is_none());
+    assert!(step(true, 0xA0, 300).is_none());
+    assert!(step(false, 0xA0, 310).is_none());
+    assert!(step(true, 0xA0, 700).is_none());
+    // Another key in between starts over
+    assert!(step(false, 0xA0, 710).is_none());
+    assert!(step(true, 0x41, 720).is_none());
+    assert!(step(true, 0xA0, 730).is_none());
+  }
+
+  // Ctrl+Alt+Shift keeps the combo clear of media apps that bind the bare key
+  #[cfg(windows)]
+  #[test]
+  fn media_key_hotkey_registers() {
+    let mask = 0x0002 | 0x0001 | 0x0004 | MOD_NOREPEAT;
+    let vk = keys::vk_from_name("MediaPlayPause".to_string()).unwrap();
+    assert_eq!(is_hotkey_available(Either::A(mask), vk).ok(), Some(true));
+    let id = next_hotkey_id().unwrap();
+    unsafe { RegisterHotKey(None, id, HOT_KEY_MODIFIERS(mask), vk) }.unwrap();
+    unsafe { UnregisterHotKey(None, id) }.unwrap();
+  }
+
+  #[cfg(windows)]
+  #[test]
+  fn set_window_title_keeps_multibyte_characters() {
+    use windows::Win32::UI::WindowsAndMessaging::{
+      CreateWindowExW, DestroyWindow, GetWindowTextW, WINDOW_STYLE,
+    };
+    use windows::core::w;
+    // Never shown; a STATIC control is the cheapest top-level window to get
+    let hwnd = unsafe {
+      CreateWindowExW(
+        WINDOW_EX_STYLE(0),
+        w!("STATIC"),
+        w!(""),
+        WINDOW_STYLE(0),
+        0,
+        0,
+        0,
+        0,
+        None,
+        None,
+        None,
+        None,
+      )
+    }
+    .unwrap();
+    let title = "Ünïcödé 窓 \u{1F980} — overlay";
+    set_window_title(hwnd, title);
+    let mut buffer = [0u16; 64];
+    let read = unsafe { GetWindowTextW(hwnd, &mut buffer) };
+    let _ = unsafe { DestroyWindow(hwnd) };
+    assert_eq!(String::from_utf16(&buffer[..read as usize]).unwrap(), title);
+  }
 }
@@ -0,0 +1,76 @@
+// --- Logging Section ---
+// Native diagnostics, routed to JS when the app asks for them, else to stderr.
+
+use std::sync::Mutex;
+
+use napi::threadsafe_function::{
+  ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::{Env, JsFunction, Result, Status};
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+
+#[derive(Clone, Copy)]
+pub(crate) enum LogLevel {
+  Error,
+  Warn,
+  Info,
+}
+
+impl LogLevel {
+  fn as_str(self) -> &'static str {
+    match self {
+      LogLevel::Error => "error",
+      LogLevel::Warn => "warn",
+      LogLevel::Info => "info",
+    }
+  }
+}
+
+/// Passed to the `set_log_callback` callback
+#[napi(object)]
+pub struct LogRecord {
+  /// `"error"`, `"warn"` or `"info"`
+  pub level: String,
+  pub message: String,
+}
+
+static LOG_CALLBACK: Lazy<
+  Mutex<Option<ThreadsafeFunction<LogRecord, ErrorStrategy::CalleeHandled>>>,
+> = Lazy::new(|| Mutex::new(None));
+
+// Safe from any thread; falls back to stderr when no callback is set or the
+// call can't be queued, e.g. while the environment shuts down
+pub(crate) fn log(level: LogLevel, message: impl Into<String>) {
+  let message = message.into();
+  if let Some(tsfn) = LOG_CALLBACK.lock().unwrap().as_ref() {
+    let record = LogRecord {
+      level: level.as_str().to_string(),
+      message: message.clone(),
+    };
+    if tsfn.call(Ok(record), ThreadsafeFunctionCallMode::NonBlocking) == Status::Ok {
+      return;
+    }
+  }
+  eprintln!("{}", message);
+}
+
+/// Sends the module's diagnostics (failed registrations, hook errors, ...)
+/// to `callback` as `LogRecord`s instead of stderr; pass `null` to go back.
+/// The callback runs asynchronously on the event loop and doesn't keep the
+/// process alive.
+#[napi]
+pub fn set_log_callback(env: Env, callback: Option<JsFunction>) -> Result<()> {
+  let tsfn = callback
+    .map(|callback| {
+      let mut tsfn = callback
+        .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<LogRecord>| {
+          Ok(vec![ctx.value])
+        })?;
+      tsfn.unref(&env)?;
+      Ok::<_, napi::Error>(tsfn)
+    })
+    .transpose()?;
+  *LOG_CALLBACK.lock().unwrap() = tsfn;
+  Ok(())
+}
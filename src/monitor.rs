@@ -0,0 +1,143 @@
+// --- Monitor Section ---
+// Display geometry for placing the overlay on multi-monitor setups.
+
+use napi::Result;
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+
+use windows::Win32::Foundation::{HWND, LPARAM, RECT};
+use windows::Win32::Graphics::Gdi::{
+  EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITOR_DEFAULTTONEAREST, MONITORINFO,
+  MonitorFromWindow,
+};
+use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
+use windows::Win32::UI::WindowsAndMessaging::MONITORINFOF_PRIMARY;
+use windows::core::{BOOL, HRESULT, s, w};
+
+/// One display, in virtual-screen coordinates: physical pixels once the
+/// process is DPI aware (as `open_webview` makes it by default), otherwise
+/// DPI-scaled logical pixels
+#[napi(object)]
+pub struct MonitorInfo {
+  pub x: i32,
+  pub y: i32,
+  pub width: i32,
+  pub height: i32,
+  pub work_area_x: i32, // the work area excludes the taskbar and docked toolbars
+  pub work_area_y: i32,
+  pub work_area_width: i32,
+  pub work_area_height: i32,
+  pub scale_factor: f64, // 1.0 = 96 DPI
+  pub is_primary: bool,
+}
+
+type GetDpiForMonitorFn = unsafe extern "system" fn(HMONITOR, i32, *mut u32, *mut u32) -> HRESULT;
+
+// GetDpiForMonitor lives in shcore.dll (Windows 8.1+), so resolve it at
+// runtime instead of linking it and failing to load on older systems.
+static GET_DPI_FOR_MONITOR: Lazy<Option<usize>> = Lazy::new(|| unsafe {
+  let shcore = LoadLibraryW(w!("shcore.dll")).ok()?;
+  GetProcAddress(shcore, s!("GetDpiForMonitor")).map(|proc| proc as usize)
+});
+
+fn monitor_scale_factor(monitor: HMONITOR) -> f64 {
+  const MDT_EFFECTIVE_DPI: i32 = 0;
+  let Some(proc) = *GET_DPI_FOR_MONITOR else {
+    return 1.0;
+  };
+  let get_dpi: GetDpiForMonitorFn = unsafe { std::mem::transmute(proc) };
+  let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+  let result = unsafe { get_dpi(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) };
+  if result.is_err() || dpi_x == 0 {
+    return 1.0;
+  }
+  dpi_x as f64 / 96.0
+}
+
+type SetProcessDpiAwarenessContextFn = unsafe extern "system" fn(isize) -> BOOL;
+
+// Per-monitor v2 awareness (Windows 10 1703+) makes the overlay render at
+// native resolution on every display instead of being bitmap-stretched.
+// Resolved at runtime for the same reason as GetDpiForMonitor; evaluated
+// at most once, and a no-op if the host already chose an awareness mode.
+static DPI_AWARENESS_SET: Lazy<bool> = Lazy::new(|| unsafe {
+  const DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2: isize = -4;
+  let Ok(user32) = LoadLibraryW(w!("user32.dll")) else {
+    return false;
+  };
+  let Some(proc) = GetProcAddress(user32, s!("SetProcessDpiAwarenessContext")) else {
+    return false;
+  };
+  let set_awareness: SetProcessDpiAwarenessContextFn = std::mem::transmute(proc);
+  set_awareness(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2).as_bool()
+});
+
+// Opts the process into per-monitor DPI awareness the first time a window
+// needs it; later calls do nothing
+pub(crate) fn ensure_dpi_awareness() {
+  Lazy::force(&DPI_AWARENESS_SET);
+}
+
+fn monitor_info(monitor: HMONITOR) -> Option<MonitorInfo> {
+  let mut info = MONITORINFO {
+    cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+    ..Default::default()
+  };
+  if !unsafe { GetMonitorInfoW(monitor, &mut info) }.as_bool() {
+    return None;
+  }
+  let (bounds, work) = (info.rcMonitor, info.rcWork);
+  Some(MonitorInfo {
+    x: bounds.left,
+    y: bounds.top,
+    width: bounds.right - bounds.left,
+    height: bounds.bottom - bounds.top,
+    work_area_x: work.left,
+    work_area_y: work.top,
+    work_area_width: work.right - work.left,
+    work_area_height: work.bottom - work.top,
+    scale_factor: monitor_scale_factor(monitor),
+    is_primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+  })
+}
+
+// The monitor `hwnd` is mostly on, or the nearest one if it's off-screen
+pub(crate) fn window_monitor(hwnd: HWND) -> Option<MonitorInfo> {
+  monitor_info(unsafe { MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST) })
+}
+
+unsafe extern "system" fn enum_monitors_proc(
+  monitor: HMONITOR,
+  _hdc: HDC,
+  _rect: *mut RECT,
+  lparam: LPARAM,
+) -> BOOL {
+  // lparam carries the Vec being filled by `list_monitors`
+  let monitors = unsafe { &mut *(lparam.0 as *mut Vec<MonitorInfo>) };
+  if let Some(info) = monitor_info(monitor) {
+    monitors.push(info);
+  }
+  true.into() // keep enumerating
+}
+
+/// Lists all displays with their work areas and DPI scale factors. The scale
+/// factor falls back to 1.0 where per-monitor DPI isn't available.
+#[napi]
+pub fn list_monitors() -> Result<Vec<MonitorInfo>> {
+  let mut monitors: Vec<MonitorInfo> = Vec::new();
+  let ok = unsafe {
+    EnumDisplayMonitors(
+      None,
+      None,
+      Some(enum_monitors_proc),
+      LPARAM(&mut monitors as *mut Vec<MonitorInfo> as isize),
+    )
+  };
+  if !ok.as_bool() {
+    return Err(napi::Error::new(
+      napi::Status::GenericFailure,
+      "Failed to enumerate monitors".to_string(),
+    ));
+  }
+  Ok(monitors)
+}
@@ -0,0 +1,206 @@
+// --- Mouse Hook Section ---
+// A WH_MOUSE_LL hook, kept apart from the keyboard hook so both can be installed.
+
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+
+use napi::threadsafe_function::{
+  ErrorStrategy, ThreadSafeCallContext, ThreadsafeFunction, ThreadsafeFunctionCallMode,
+};
+use napi::{Env, JsFunction, Result};
+use napi_derive::napi;
+use once_cell::sync::Lazy;
+
+use windows::Win32::Foundation::{LPARAM, LRESULT, POINT, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::WindowsAndMessaging::{
+  CallNextHookEx, GetCursorPos, HC_ACTION, MSG, MSLLHOOKSTRUCT, PM_NOREMOVE, PeekMessageW,
+  PostThreadMessageW, SetWindowsHookExW, UnhookWindowsHookEx, WH_MOUSE_LL, WM_QUIT,
+};
+
+use crate::logging::{LogLevel, log};
+use crate::{Pumped, SafeHhook, ensure_cleanup_hook, hook_module, run_message_loop};
+
+/// Payload passed to a mouse hook callback, copied from MSLLHOOKSTRUCT
+#[napi(object)]
+pub struct MouseEvent {
+  pub event: u32, // window message, e.g. 0x0207 (WM_MBUTTONDOWN)
+  pub x: i32,     // cursor position in screen coordinates
+  pub y: i32,
+  pub mouse_data: u32, // wheel delta or X button in the high word, else 0
+  pub time: u32,       // message timestamp in ms (GetTickCount clock)
+}
+
+type MouseCallback = ThreadsafeFunction<MouseEvent, ErrorStrategy::CalleeHandled>;
+
+// One installed mouse hook, from `register_mouse_hook` until
+// `unregister_mouse_hook`
+struct MouseHook {
+  callback: MouseCallback,
+  thread_id: Option<u32>, // Set together with `hook` once the thread is running
+  hook: Option<SafeHhook>,
+}
+
+// Every installed mouse hook, keyed by the ID `register_mouse_hook` returns
+static MOUSE_HOOKS: Lazy<Mutex<HashMap<u32, MouseHook>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_MOUSE_HOOK_ID: AtomicU32 = AtomicU32::new(1);
+
+thread_local! {
+  // The `MOUSE_HOOKS` entry this hook thread serves, as `CURRENT_KEY_HOOK`
+  // does for key hooks
+  static CURRENT_MOUSE_HOOK: Cell<u32> = const { Cell::new(0) };
+}
+
+extern "system" fn mouse_proc(code: i32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+  unsafe {
+    if code == HC_ACTION as i32 {
+      let mouse = *(lparam.0 as *const MSLLHOOKSTRUCT);
+      // Called on a clone so the registry isn't locked while the call queues
+      let id = CURRENT_MOUSE_HOOK.get();
+      let tsfn = MOUSE_HOOKS
+        .lock()
+        .unwrap()
+        .get(&id)
+        .map(|entry| entry.callback.clone());
+      if let Some(tsfn) = tsfn {
+        let event = MouseEvent {
+          event: wparam.0 as u32,
+          x: mouse.pt.x,
+          y: mouse.pt.y,
+          mouse_data: mouse.mouseData,
+          time: mouse.time,
+        };
+        let _ = tsfn.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+      }
+    }
+    CallNextHookEx(None, code, wparam, lparam)
+  }
+}
+
+/// Calls `callback` for every low-level mouse event (moves included) until
+/// `unregister_mouse_hook` and returns the hook's ID. Several hooks can be
+/// installed at once, each with its own thread. Keep the callback cheap:
+/// moves arrive at the pointer's report rate. If the hook can't be
+/// installed, the callback receives the error instead.
+#[napi]
+pub fn register_mouse_hook(mut env: Env, callback: JsFunction) -> Result<u32> {
+  ensure_cleanup_hook(&mut env)?;
+  let callback = callback
+    .create_threadsafe_function(0, |ctx: ThreadSafeCallContext<MouseEvent>| {
+      Ok(vec![ctx.value])
+    })?;
+  let id = NEXT_MOUSE_HOOK_ID.fetch_add(1, Ordering::SeqCst);
+  MOUSE_HOOKS.lock().unwrap().insert(
+    id,
+    MouseHook {
+      callback,
+      thread_id: None,
+      hook: None,
+    },
+  );
+
+  thread::spawn(move || unsafe {
+    CURRENT_MOUSE_HOOK.set(id);
+    // Create the message queue before the thread ID is published, or a
+    // WM_QUIT posted right away would be lost
+    let mut msg = MSG::default();
+    let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+    let installed = {
+      let mut hooks = MOUSE_HOOKS.lock().unwrap();
+      let Some(entry) = hooks.get_mut(&id) else {
+        return; // unregistered before this thread got going
+      };
+      let hook = hook_module()
+        .and_then(|module| SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_proc), Some(module), 0));
+      match hook {
+        Ok(hook) => {
+          entry.hook = Some(SafeHhook(hook));
+          entry.thread_id = Some(GetCurrentThreadId());
+          Ok(())
+        }
+        Err(e) => Err((e, hooks.remove(&id))),
+      }
+    };
+    if let Err((e, entry)) = installed {
+      log(
+        LogLevel::Error,
+        format!("SetWindowsHookExW failed: {:?}", e),
+      );
+      if let Some(entry) = entry {
+        let _ = entry.callback.call(
+          Err(napi::Error::new(
+            napi::Status::GenericFailure,
+            format!("Failed to install mouse hook: {}", e),
+          )),
+          ThreadsafeFunctionCallMode::NonBlocking,
+        );
+      }
+      return;
+    }
+
+    // The hook is serviced by this thread's message loop; WM_QUIT comes
+    // from `unregister_mouse_hook`
+    run_message_loop("mouse hook", |_| Pumped::Dispatch);
+
+    // Still registered only if GetMessageW failed
+    remove_mouse_hooks(Some(id));
+  });
+
+  Ok(id)
+}
+
+// Removes mouse hook `id`, or every mouse hook, unhooking right here in case
+// a hook thread is never scheduled again. A thread still starting finds its
+// entry gone and exits without hooking.
+fn remove_mouse_hooks(id: Option<u32>) {
+  let removed: Vec<MouseHook> = {
+    let mut hooks = MOUSE_HOOKS.lock().unwrap();
+    match id {
+      Some(id) => hooks.remove(&id).into_iter().collect(),
+      None => hooks.drain().map(|(_, entry)| entry).collect(),
+    }
+  };
+  for entry in removed {
+    if let Some(SafeHhook(h)) = entry.hook {
+      let _ = unsafe { UnhookWindowsHookEx(h) };
+    }
+    if let Some(tid) = entry.thread_id {
+      let _ = unsafe { PostThreadMessageW(tid, WM_QUIT, WPARAM(0), LPARAM(0)) };
+    }
+  }
+}
+
+/// Removes the mouse hook `id` returned by `register_mouse_hook`, or every
+/// mouse hook when `id` is omitted. A no-op for hooks that are already gone.
+#[napi]
+pub fn unregister_mouse_hook(id: Option<u32>) -> Result<()> {
+  remove_mouse_hooks(id);
+  Ok(())
+}
+
+/// A point in virtual-screen coordinates, in the same pixels `list_monitors` uses
+#[napi(object)]
+pub struct CursorPosition {
+  pub x: i32,
+  pub y: i32,
+}
+
+/// Where the mouse cursor is right now, e.g. to open the overlay on the
+/// monitor under it. Fails while a secure desktop (UAC, lock screen) is up.
+#[napi]
+pub fn get_cursor_position() -> Result<CursorPosition> {
+  let mut point = POINT::default();
+  unsafe { GetCursorPos(&mut point) }.map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to read cursor position: {}", e),
+    )
+  })?;
+  Ok(CursorPosition {
+    x: point.x,
+    y: point.y,
+  })
+}
@@ -0,0 +1,102 @@
+// --- Thumbnail Section ---
+// Live window previews drawn by DWM into another window, e.g. the overlay.
+
+use std::sync::Mutex;
+
+use napi::Result;
+use napi_derive::napi;
+
+use windows::Win32::Foundation::RECT;
+use windows::Win32::Graphics::Dwm::{
+  DWM_THUMBNAIL_PROPERTIES, DWM_TNP_RECTDESTINATION, DWM_TNP_SOURCECLIENTAREAONLY, DWM_TNP_VISIBLE,
+  DwmRegisterThumbnail, DwmUnregisterThumbnail, DwmUpdateThumbnailProperties,
+};
+
+use crate::window::{WindowRect, checked_hwnd};
+
+// Points the thumbnail at `rect` (destination client coordinates) and shows it
+fn place_thumbnail(thumbnail: isize, rect: &WindowRect) -> windows::core::Result<()> {
+  let properties = DWM_THUMBNAIL_PROPERTIES {
+    dwFlags: DWM_TNP_RECTDESTINATION | DWM_TNP_VISIBLE | DWM_TNP_SOURCECLIENTAREAONLY,
+    rcDestination: RECT {
+      left: rect.x,
+      top: rect.y,
+      right: rect.x + rect.width,
+      bottom: rect.y + rect.height,
+    },
+    fVisible: true.into(),
+    fSourceClientAreaOnly: false.into(), // include the title bar, like Alt-Tab
+    ..Default::default()
+  };
+  unsafe { DwmUpdateThumbnailProperties(thumbnail, &properties) }
+}
+
+/// Returned by `register_window_thumbnail`; the preview stays until
+/// `unregister`, the handle is garbage collected, or either window closes
+#[napi]
+pub struct ThumbnailHandle {
+  thumbnail: Mutex<Option<isize>>, // DWM thumbnail ID; `None` once unregistered
+}
+
+#[napi]
+impl ThumbnailHandle {
+  /// Moves or resizes the preview within the destination window. DWM keeps
+  /// the source's aspect ratio, fitting it inside `rect`.
+  #[napi]
+  pub fn set_rect(&self, rect: WindowRect) -> Result<()> {
+    let Some(thumbnail) = *self.thumbnail.lock().unwrap() else {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Thumbnail is unregistered".to_string(),
+      ));
+    };
+    place_thumbnail(thumbnail, &rect).map_err(|e| {
+      napi::Error::new(
+        napi::Status::GenericFailure,
+        format!("Failed to update thumbnail: {}", e),
+      )
+    })
+  }
+
+  /// Removes the preview. Calling this more than once is a no-op.
+  #[napi]
+  pub fn unregister(&self) -> Result<()> {
+    if let Some(thumbnail) = self.thumbnail.lock().unwrap().take() {
+      let _ = unsafe { DwmUnregisterThumbnail(thumbnail) };
+    }
+    Ok(())
+  }
+}
+
+impl Drop for ThumbnailHandle {
+  fn drop(&mut self) {
+    let _ = self.unregister();
+  }
+}
+
+/// Shows a live preview of `sourceHwnd` inside `destHwnd` (e.g. the overlay,
+/// via `WebviewHandle.get_hwnd`) at `rect`, in the destination's client
+/// coordinates. The destination must be a top-level window of this process.
+#[napi]
+pub fn register_window_thumbnail(
+  source_hwnd: i64,
+  dest_hwnd: i64,
+  rect: WindowRect,
+) -> Result<ThumbnailHandle> {
+  let source = checked_hwnd(source_hwnd)?;
+  let dest = checked_hwnd(dest_hwnd)?;
+  let thumbnail = unsafe { DwmRegisterThumbnail(dest, source) }.map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!(
+        "Failed to register thumbnail of window {}: {}",
+        source_hwnd, e
+      ),
+    )
+  })?;
+  let handle = ThumbnailHandle {
+    thumbnail: Mutex::new(Some(thumbnail)),
+  };
+  handle.set_rect(rect)?;
+  Ok(handle)
+}
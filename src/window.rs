@@ -0,0 +1,1005 @@
+// --- Window Management Section ---
+// Enumeration and control of other applications' top-level windows.
+
+use std::ffi::c_void;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use napi::Result;
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+
+use windows::Win32::Foundation::{
+  CloseHandle, GetLastError, HANDLE, HWND, LPARAM, POINT, RECT, SetLastError, WIN32_ERROR, WPARAM,
+};
+use windows::Win32::Graphics::Dwm::{
+  DWM_CLOAKED_SHELL, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS, DwmGetWindowAttribute,
+};
+use windows::Win32::Graphics::Gdi::{
+  BI_RGB, BITMAPINFO, BITMAPINFOHEADER, CreateCompatibleDC, CreateDIBSection, DIB_RGB_COLORS,
+  DeleteDC, DeleteObject, HGDIOBJ, SelectObject,
+};
+use windows::Win32::Security::{
+  GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, TOKEN_MANDATORY_LABEL,
+  TOKEN_QUERY, TokenIntegrityLevel,
+};
+use windows::Win32::Storage::Xps::{PRINT_WINDOW_FLAGS, PrintWindow};
+use windows::Win32::System::Com::{
+  CLSCTX_ALL, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx, CoUninitialize,
+};
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::System::Threading::{
+  AttachThreadInput, GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId, OpenProcess,
+  OpenProcessToken, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION,
+  QueryFullProcessImageNameW,
+};
+use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+use windows::Win32::UI::Shell::{IVirtualDesktopManager, VirtualDesktopManager};
+use windows::Win32::UI::WindowsAndMessaging::{
+  BeginDeferWindowPos, BringWindowToTop, DeferWindowPos, EndDeferWindowPos, EnumWindows,
+  FLASHW_TIMER, FLASHW_TRAY, FLASHWINFO, FlashWindowEx, GA_ROOT, GCLP_HICON, GCLP_HICONSM,
+  GWL_EXSTYLE, GetAncestor, GetClassLongPtrW, GetDesktopWindow, GetForegroundWindow, GetIconInfo,
+  GetShellWindow, GetWindowLongW, GetWindowRect, GetWindowTextLengthW, GetWindowTextW,
+  GetWindowThreadProcessId, HICON, HWND_NOTOPMOST, HWND_TOPMOST, ICON_BIG, ICON_SMALL, ICON_SMALL2,
+  ICONINFO, IsIconic, IsWindow, IsWindowVisible, IsZoomed, PW_RENDERFULLCONTENT, SHOW_WINDOW_CMD,
+  SMTO_ABORTIFHUNG, SPI_GETFOREGROUNDLOCKTIMEOUT, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE,
+  SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+  SendMessageTimeoutW, SetForegroundWindow, SetWindowPos, ShowWindow, SystemParametersInfoW,
+  WM_GETICON, WS_EX_TOOLWINDOW, WindowFromPoint,
+};
+use windows::core::{BOOL, PWSTR};
+
+use crate::image::{Pixels, bgra_len, encode_png, read_bitmap};
+use crate::input::nudge_input;
+
+/// A top-level window as seen by the switcher
+#[napi(object)]
+pub struct WindowInfo {
+  pub hwnd: i64,
+  pub title: String,
+  pub process_id: u32,
+  /// Executable file name, e.g. `chrome.exe`; empty if the process can't be
+  /// queried (elevated or protected processes)
+  pub process_name: String,
+  pub is_minimized: bool, // IsIconic
+  pub is_maximized: bool, // IsZoomed
+}
+
+// HWNDs cross the JS boundary as plain numbers
+pub(crate) fn hwnd_from_i64(hwnd: i64) -> HWND {
+  HWND(hwnd as isize as *mut _)
+}
+
+pub(crate) fn hwnd_to_i64(hwnd: HWND) -> i64 {
+  hwnd.0 as isize as i64
+}
+
+// Converts a JS hwnd, rejecting anything `IsWindow` doesn't recognise
+pub(crate) fn checked_hwnd(hwnd: i64) -> Result<HWND> {
+  let handle = hwnd_from_i64(hwnd);
+  if !unsafe { IsWindow(Some(handle)) }.as_bool() {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid window handle: {}", hwnd),
+    ));
+  }
+  Ok(handle)
+}
+
+// Restores `hwnd` if minimized and brings it to the foreground. Windows only
+// lets the foreground thread change the foreground window, so we briefly
+// attach our input queue to that thread's. Returns whether it was accepted.
+pub(crate) fn activate_window(hwnd: HWND) -> bool {
+  try_activate_window(hwnd).is_ok()
+}
+
+// `activate_window`, keeping GetLastError from a refusal (0 if Windows set none)
+fn try_activate_window(hwnd: HWND) -> std::result::Result<(), u32> {
+  unsafe {
+    if IsIconic(hwnd).as_bool() {
+      let _ = ShowWindow(hwnd, SW_RESTORE);
+    }
+
+    let current_thread = GetCurrentThreadId();
+    let foreground_thread = GetWindowThreadProcessId(GetForegroundWindow(), None);
+    let attached = foreground_thread != 0
+      && foreground_thread != current_thread
+      && AttachThreadInput(current_thread, foreground_thread, true).as_bool();
+
+    let _ = BringWindowToTop(hwnd);
+    SetLastError(WIN32_ERROR(0));
+    let accepted = SetForegroundWindow(hwnd).as_bool();
+    let code = GetLastError().0; // before AttachThreadInput overwrites it
+
+    if attached {
+      let _ = AttachThreadInput(current_thread, foreground_thread, false);
+    }
+    if accepted { Ok(()) } else { Err(code) }
+  }
+}
+
+// The full caption of `hwnd`, however long. The length can change between
+// the two calls, so the read's own count decides what's kept.
+fn window_title(hwnd: HWND) -> String {
+  let len = unsafe { GetWindowTextLengthW(hwnd) };
+  if len <= 0 {
+    return String::new();
+  }
+  let mut buffer = vec![0u16; len as usize + 1]; // room for the terminator
+  let read = unsafe { GetWindowTextW(hwnd, &mut buffer) };
+  decode_title(&buffer, read)
+}
+
+// The first `read` UTF-16 units of `buffer`, up to any terminator. A read
+// cut short right after a high surrogate (the title shrank in between)
+// drops it, rather than ending the title in U+FFFD.
+fn decode_title(buffer: &[u16], read: i32) -> String {
+  let mut units = &buffer[..(read.max(0) as usize).min(buffer.len())];
+  if let Some(end) = units.iter().position(|&unit| unit == 0) {
+    units = &units[..end];
+  }
+  if let [rest @ .., 0xD800..=0xDBFF] = units {
+    units = rest;
+  }
+  String::from_utf16_lossy(units)
+}
+
+// The executable name of `process_id`, or "" if we aren't allowed to look
+fn process_name(process_id: u32) -> String {
+  let Ok(process) = (unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) })
+  else {
+    return String::new();
+  };
+  let mut buffer = [0u16; 1024];
+  let mut len = buffer.len() as u32;
+  let queried = unsafe {
+    QueryFullProcessImageNameW(
+      process,
+      PROCESS_NAME_WIN32,
+      PWSTR(buffer.as_mut_ptr()),
+      &mut len,
+    )
+  };
+  let _ = unsafe { CloseHandle(process) };
+  if queried.is_err() {
+    return String::new();
+  }
+  let path = String::from_utf16_lossy(&buffer[..len as usize]);
+  path.rsplit('\\').next().unwrap_or_default().to_string()
+}
+
+/// Narrows what `list_windows` returns; checked during enumeration, so
+/// filtered-out windows cost no process lookups and never reach JS
+#[napi(object)]
+#[derive(Default)]
+pub struct ListWindowsOptions {
+  /// Leave out windows of these processes, e.g. this app's own
+  pub exclude_process_ids: Option<Vec<u32>>,
+  /// Keep minimized windows (default true)
+  pub include_minimized: Option<bool>,
+  /// Keep only windows whose title contains this text, ignoring case
+  pub title_pattern: Option<String>,
+  /// Keep EnumWindows' top-to-bottom z-order (default true); false sorts by
+  /// `hwnd` instead, for a list that stays put as focus moves
+  pub sort_by_z_order: Option<bool>,
+}
+
+// What `enum_windows_proc` fills, and the `ListWindowsOptions` it checks
+struct EnumState {
+  windows: Vec<WindowInfo>,
+  exclude_process_ids: Vec<u32>,
+  include_minimized: bool,
+  title_pattern: Option<String>, // already lowercased
+}
+
+unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+  // lparam carries the state of the `list_windows` call
+  let state = unsafe { &mut *(lparam.0 as *mut EnumState) };
+
+  if !unsafe { IsWindowVisible(hwnd) }.as_bool() {
+    return true.into();
+  }
+  let ex_style = unsafe { GetWindowLongW(hwnd, GWL_EXSTYLE) } as u32;
+  if ex_style & WS_EX_TOOLWINDOW.0 != 0 {
+    return true.into();
+  }
+  let title = window_title(hwnd);
+  if title.is_empty() {
+    return true.into();
+  }
+  if let Some(pattern) = &state.title_pattern
+    && !title.to_lowercase().contains(pattern.as_str())
+  {
+    return true.into();
+  }
+  let is_minimized = unsafe { IsIconic(hwnd) }.as_bool();
+  if is_minimized && !state.include_minimized {
+    return true.into();
+  }
+
+  let mut process_id = 0u32;
+  unsafe { GetWindowThreadProcessId(hwnd, Some(&mut process_id)) };
+  if state.exclude_process_ids.contains(&process_id) {
+    return true.into();
+  }
+  state.windows.push(WindowInfo {
+    hwnd: hwnd_to_i64(hwnd),
+    title,
+    process_id,
+    process_name: process_name(process_id),
+    is_minimized,
+    is_maximized: unsafe { IsZoomed(hwnd) }.as_bool(),
+  });
+  true.into() // keep enumerating
+}
+
+// Balances a successful CoInitializeEx when dropped
+struct ComScope(bool);
+
+impl ComScope {
+  fn enter() -> Self {
+    // S_FALSE (already initialized) still needs a matching CoUninitialize;
+    // RPC_E_CHANGED_MODE means COM is up in another mode, usable as is
+    let hr = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+    ComScope(hr.is_ok())
+  }
+}
+
+impl Drop for ComScope {
+  fn drop(&mut self) {
+    if self.0 {
+      unsafe { CoUninitialize() };
+    }
+  }
+}
+
+// Why DWM is hiding `hwnd` (DWM_CLOAKED_* bits), or 0 if it isn't
+fn cloak_reason(hwnd: HWND) -> u32 {
+  let mut reason = 0u32;
+  let read = unsafe {
+    DwmGetWindowAttribute(
+      hwnd,
+      DWMWA_CLOAKED,
+      &mut reason as *mut u32 as *mut c_void,
+      size_of::<u32>() as u32,
+    )
+  };
+  if read.is_ok() { reason } else { 0 }
+}
+
+/// Lists visible, titled top-level windows (tool windows excluded), in
+/// z-order from top to bottom, as `EnumWindows` reports them, unless
+/// `sortByZOrder` is false. Windows DWM
+/// keeps cloaked, such as suspended UWP apps, are left out, as are windows
+/// on other virtual desktops with `currentDesktopOnly`, like Alt-Tab does;
+/// where the virtual desktop API is unavailable every desktop is listed.
+/// `options` filters further.
+#[napi]
+pub fn list_windows(
+  current_desktop_only: Option<bool>,
+  options: Option<ListWindowsOptions>,
+) -> Result<Vec<WindowInfo>> {
+  let options = options.unwrap_or_default();
+  let mut state = EnumState {
+    windows: Vec::new(),
+    exclude_process_ids: options.exclude_process_ids.unwrap_or_default(),
+    include_minimized: options.include_minimized.unwrap_or(true),
+    title_pattern: options.title_pattern.map(|pattern| pattern.to_lowercase()),
+  };
+  unsafe {
+    EnumWindows(
+      Some(enum_windows_proc),
+      LPARAM(&mut state as *mut EnumState as isize),
+    )
+  }
+  .map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to enumerate windows: {}", e),
+    )
+  })?;
+
+  let mut windows = state.windows;
+  if !options.sort_by_z_order.unwrap_or(true) {
+    windows.sort_by_key(|window| window.hwnd);
+  }
+  let current_desktop_only = current_desktop_only.unwrap_or(false);
+  let _com = ComScope::enter();
+  let manager: Option<IVirtualDesktopManager> =
+    unsafe { CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL) }.ok();
+  windows.retain(|window| {
+    let hwnd = hwnd_from_i64(window.hwnd);
+    // `None` where the manager can't place the window
+    let on_current_desktop = || {
+      let manager = manager.as_ref()?;
+      unsafe { manager.IsWindowOnCurrentVirtualDesktop(hwnd) }
+        .ok()
+        .map(|on_current| on_current.as_bool())
+    };
+    match cloak_reason(hwnd) {
+      // Keep windows the manager can't place rather than hide them
+      0 => !current_desktop_only || on_current_desktop().unwrap_or(true),
+      // The shell cloaks both other desktops' windows and hidden app frames
+      DWM_CLOAKED_SHELL => !current_desktop_only && on_current_desktop() == Some(false),
+      _ => false,
+    }
+  });
+  Ok(windows)
+}
+
+/// What `focus_window` achieved
+#[napi]
+pub enum FocusStatus {
+  Focused,
+  /// The window runs at a higher integrity level than this process (e.g. an
+  /// elevated app), so Windows won't let us activate it
+  Elevated,
+  /// Another app holds the foreground lock: the user gave it input more
+  /// recently than the system's foreground lock timeout. `flash_window` is
+  /// the usual fallback.
+  ForegroundLocked,
+  /// SetForegroundWindow refused for some other reason
+  Refused,
+}
+
+/// Returned by `focus_window`
+#[napi(object)]
+pub struct FocusResult {
+  pub status: FocusStatus,
+  /// GetLastError after SetForegroundWindow refused, for diagnostics; 0
+  /// when focused or when Windows gave no code, as it often doesn't
+  pub code: u32,
+}
+
+// Whether the foreground lock is in effect against us right now: another
+// process owns the foreground and the user's last input came within the
+// lock timeout (the timeout only applies then). LockSetForegroundWindow
+// locks can't be observed and come out as `Refused`.
+fn foreground_locked() -> bool {
+  let foreground = unsafe { GetForegroundWindow() };
+  if foreground.is_invalid() {
+    return false;
+  }
+  let mut foreground_process = 0u32;
+  unsafe { GetWindowThreadProcessId(foreground, Some(&mut foreground_process)) };
+  if foreground_process == unsafe { GetCurrentProcessId() } {
+    return false;
+  }
+  let mut lock_timeout = 0u32;
+  let read = unsafe {
+    SystemParametersInfoW(
+      SPI_GETFOREGROUNDLOCKTIMEOUT,
+      0,
+      Some(&mut lock_timeout as *mut u32 as *mut c_void),
+      SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+    )
+  };
+  let mut last_input = LASTINPUTINFO {
+    cbSize: size_of::<LASTINPUTINFO>() as u32,
+    ..Default::default()
+  };
+  if read.is_err() || !unsafe { GetLastInputInfo(&mut last_input) }.as_bool() {
+    return false;
+  }
+  // Both are GetTickCount values, so the difference survives wraparound
+  let idle_ms = unsafe { GetTickCount() }.wrapping_sub(last_input.dwTime);
+  idle_ms < lock_timeout
+}
+
+/// Options for `focus_window` and `focus_window_sync`
+#[napi(object)]
+#[derive(Default)]
+pub struct FocusOptions {
+  /// Inject a zero-distance mouse move first, so Windows sees this process
+  /// as having just received input and lets it take the foreground more
+  /// often (default false). The move is real input as far as the system is
+  /// concerned: it resets idle timers and reaches mouse hooks, including
+  /// `register_mouse_hook`, so it's opt-in. It doesn't help against
+  /// elevated windows, and a helper process can instead be granted the
+  /// right outright with `AllowSetForegroundWindow` on its side.
+  pub nudge_input: Option<bool>,
+}
+
+// Applies `options`, then brings `hwnd` to the foreground
+fn activate_with(hwnd: HWND, options: Option<FocusOptions>) -> std::result::Result<(), u32> {
+  if options.unwrap_or_default().nudge_input.unwrap_or(false) {
+    nudge_input();
+  }
+  try_activate_window(hwnd)
+}
+
+/// Brings `hwnd` to the foreground, restoring it first if minimized. Windows
+/// may refuse; the result says why. Throws only for an invalid handle.
+#[napi]
+pub fn focus_window(hwnd: i64, options: Option<FocusOptions>) -> Result<FocusResult> {
+  let handle = checked_hwnd(hwnd)?;
+  let Err(code) = activate_with(handle, options) else {
+    return Ok(FocusResult {
+      status: FocusStatus::Focused,
+      code: 0,
+    });
+  };
+
+  let own_level = integrity_level(unsafe { GetCurrentProcess() });
+  let status = match (window_integrity_level(handle), own_level) {
+    (Some(target), Some(own)) if target > own => FocusStatus::Elevated,
+    _ if foreground_locked() => FocusStatus::ForegroundLocked,
+    _ => FocusStatus::Refused,
+  };
+  Ok(FocusResult { status, code })
+}
+
+// Longest sleep between foreground checks in `focus_window_sync`
+const FOCUS_POLL_MS: u64 = 10;
+
+/// Like `focus_window`, but waits up to `timeoutMs` for `hwnd` to actually
+/// become the foreground window, so keystrokes sent next land in it.
+/// Returns whether it did. Throws only for an invalid handle.
+#[napi]
+pub fn focus_window_sync(
+  hwnd: i64,
+  timeout_ms: u32,
+  options: Option<FocusOptions>,
+) -> Result<bool> {
+  let handle = checked_hwnd(hwnd)?;
+  let _ = activate_with(handle, options);
+  let deadline = Instant::now() + Duration::from_millis(timeout_ms.into());
+  loop {
+    if unsafe { GetForegroundWindow() } == handle {
+      return Ok(true);
+    }
+    let now = Instant::now();
+    if now >= deadline {
+      return Ok(false);
+    }
+    thread::sleep((deadline - now).min(Duration::from_millis(FOCUS_POLL_MS)));
+  }
+}
+
+/// Pins `hwnd` above all non-topmost windows, or releases it again, without
+/// moving or activating it. Windows of elevated apps refuse unless this
+/// process is elevated too.
+#[napi]
+pub fn set_window_topmost(hwnd: i64, on_top: bool) -> Result<()> {
+  let handle = checked_hwnd(hwnd)?;
+  let insert_after = if on_top { HWND_TOPMOST } else { HWND_NOTOPMOST };
+  unsafe {
+    SetWindowPos(
+      handle,
+      Some(insert_after),
+      0,
+      0,
+      0,
+      0,
+      SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+    )
+  }
+  .map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to change topmost state of window {}: {}", hwnd, e),
+    )
+  })
+}
+
+/// Flashes `hwnd`'s taskbar button `count` times without taking focus, a
+/// quieter alternative to `focus_window` for notifications.
+#[napi]
+pub fn flash_window(hwnd: i64, count: u32) -> Result<()> {
+  let handle = checked_hwnd(hwnd)?;
+  let info = FLASHWINFO {
+    cbSize: std::mem::size_of::<FLASHWINFO>() as u32,
+    hwnd: handle,
+    dwFlags: FLASHW_TRAY | FLASHW_TIMER,
+    uCount: count,
+    dwTimeout: 0, // default cursor blink rate
+  };
+  // The return value is the previous flash state, not success
+  let _ = unsafe { FlashWindowEx(&info) };
+  Ok(())
+}
+
+// Asks the window for its icon (largest first), then falls back to its class icon.
+// The handle is owned by the window/class, so it must not be destroyed.
+fn window_icon_handle(hwnd: HWND) -> Option<HICON> {
+  for kind in [ICON_BIG, ICON_SMALL2, ICON_SMALL] {
+    let mut result = 0usize;
+    unsafe {
+      // A hung window must not stall the switcher
+      SendMessageTimeoutW(
+        hwnd,
+        WM_GETICON,
+        WPARAM(kind as usize),
+        LPARAM(0),
+        SMTO_ABORTIFHUNG,
+        100,
+        Some(&mut result),
+      )
+    };
+    if result != 0 {
+      return Some(HICON(result as *mut _));
+    }
+  }
+  [GCLP_HICON, GCLP_HICONSM]
+    .into_iter()
+    .map(|index| unsafe { GetClassLongPtrW(hwnd, index) })
+    .find(|&icon| icon != 0)
+    .map(|icon| HICON(icon as *mut _))
+}
+
+// Renders an icon to PNG, taking transparency from the mask when the color
+// bitmap has no alpha channel (older, non-32bpp icons).
+fn icon_to_png(icon: HICON) -> Option<Vec<u8>> {
+  let mut info = ICONINFO::default();
+  unsafe { GetIconInfo(icon, &mut info) }.ok()?;
+
+  let mut pixels = read_bitmap(info.hbmColor);
+  if let Some(pixels) = pixels.as_mut()
+    && pixels.bgra.chunks_exact(4).all(|pixel| pixel[3] == 0)
+  {
+    let mask = read_bitmap(info.hbmMask);
+    for (index, pixel) in pixels.bgra.chunks_exact_mut(4).enumerate() {
+      // Mask pixels are white where the icon is transparent
+      let transparent = mask
+        .as_ref()
+        .and_then(|mask| mask.bgra.get(index * 4))
+        .is_some_and(|&value| value != 0);
+      pixel[3] = if transparent { 0 } else { 255 };
+    }
+  }
+
+  // GetIconInfo hands us copies of both bitmaps
+  unsafe {
+    let _ = DeleteObject(HGDIOBJ(info.hbmColor.0));
+    let _ = DeleteObject(HGDIOBJ(info.hbmMask.0));
+  }
+  encode_png(&pixels?)
+}
+
+/// The window's icon as a PNG, or `null` if it has none.
+#[napi]
+pub fn get_window_icon(hwnd: i64) -> Result<Option<Buffer>> {
+  let handle = checked_hwnd(hwnd)?;
+  Ok(
+    window_icon_handle(handle)
+      .and_then(icon_to_png)
+      .map(Buffer::from),
+  )
+}
+
+// Renders `hwnd` into a top-down 32bpp DIB section the size of its window
+// rect. PW_RENDERFULLCONTENT goes through DWM, so GPU-drawn content
+// (browsers, the webview) comes out instead of a black frame.
+fn print_window(hwnd: HWND) -> Option<Pixels> {
+  let mut rect = RECT::default();
+  unsafe { GetWindowRect(hwnd, &mut rect) }.ok()?;
+  let (width, height) = (rect.right - rect.left, rect.bottom - rect.top);
+  if width <= 0 || height <= 0 {
+    return None;
+  }
+  let len = bgra_len(width as u32, height as u32)?;
+  let header = BITMAPINFO {
+    bmiHeader: BITMAPINFOHEADER {
+      biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
+      biWidth: width,
+      biHeight: -height, // negative height = top-down rows
+      biPlanes: 1,
+      biBitCount: 32,
+      biCompression: BI_RGB.0,
+      ..Default::default()
+    },
+    ..Default::default()
+  };
+
+  let dc = unsafe { CreateCompatibleDC(None) };
+  if dc.is_invalid() {
+    return None;
+  }
+  let mut bits: *mut c_void = std::ptr::null_mut();
+  let pixels = unsafe { CreateDIBSection(Some(dc), &header, DIB_RGB_COLORS, &mut bits, None, 0) }
+    .ok()
+    .and_then(|bitmap| {
+      let previous = unsafe { SelectObject(dc, HGDIOBJ(bitmap.0)) };
+      let printed =
+        unsafe { PrintWindow(hwnd, dc, PRINT_WINDOW_FLAGS(PW_RENDERFULLCONTENT)) }.as_bool();
+      let pixels = printed.then(|| {
+        let mut bgra = unsafe { std::slice::from_raw_parts(bits as *const u8, len) }.to_vec();
+        // GDI leaves the alpha byte at 0; the capture is opaque
+        for pixel in bgra.chunks_exact_mut(4) {
+          pixel[3] = 255;
+        }
+        Pixels {
+          width: width as u32,
+          height: height as u32,
+          bgra,
+        }
+      });
+      unsafe {
+        SelectObject(dc, previous);
+        let _ = DeleteObject(HGDIOBJ(bitmap.0));
+      }
+      pixels
+    });
+  let _ = unsafe { DeleteDC(dc) };
+  pixels
+}
+
+/// A PNG screenshot of the whole window, frame included, e.g. of the overlay
+/// (via `WebviewHandle.get_hwnd`) or of a window DWM won't thumbnail. Works
+/// for windows covered by others, but minimized ones have nothing to show.
+/// `null` if the handle is invalid or the capture fails.
+#[napi]
+pub fn capture_window(hwnd: i64) -> Option<Buffer> {
+  let handle = hwnd_from_i64(hwnd);
+  if !unsafe { IsWindow(Some(handle)) }.as_bool() {
+    return None;
+  }
+  print_window(handle)
+    .as_ref()
+    .and_then(encode_png)
+    .map(Buffer::from)
+}
+
+/// The window the user is currently working in, or `null` if there is none
+/// (e.g. while focus is changing). Call it from a hotkey callback to remember
+/// where to return focus later.
+#[napi]
+pub fn get_foreground_window() -> Option<i64> {
+  let hwnd = unsafe { GetForegroundWindow() };
+  if hwnd.is_invalid() {
+    None
+  } else {
+    Some(hwnd_to_i64(hwnd))
+  }
+}
+
+/// The top-level window at screen point (`x`, `y`), e.g. under the cursor
+/// from `get_cursor_position`, or `null` over the desktop or one of this
+/// process's own windows such as the overlay. Hidden and disabled windows
+/// are skipped, as they are for mouse clicks.
+#[napi]
+pub fn window_from_point(x: i32, y: i32) -> Option<i64> {
+  let hwnd = unsafe { WindowFromPoint(POINT { x, y }) };
+  if hwnd.is_invalid() {
+    return None;
+  }
+  // WindowFromPoint stops at the deepest child, e.g. a button
+  let root = unsafe { GetAncestor(hwnd, GA_ROOT) };
+  if root.is_invalid()
+    || root == unsafe { GetDesktopWindow() }
+    || root == unsafe { GetShellWindow() }
+  {
+    return None;
+  }
+  let mut process_id = 0u32;
+  unsafe { GetWindowThreadProcessId(root, Some(&mut process_id)) };
+  if process_id == unsafe { GetCurrentProcessId() } {
+    return None;
+  }
+  Some(hwnd_to_i64(root))
+}
+
+// ShowWindow's return value is the previous visibility, not success, so only
+// the handle check can fail
+fn show_window(hwnd: i64, command: SHOW_WINDOW_CMD) -> Result<()> {
+  let handle = checked_hwnd(hwnd)?;
+  let _ = unsafe { ShowWindow(handle, command) };
+  Ok(())
+}
+
+/// Minimizes `hwnd` to the taskbar.
+#[napi]
+pub fn minimize_window(hwnd: i64) -> Result<()> {
+  show_window(hwnd, SW_MINIMIZE)
+}
+
+/// Maximizes `hwnd` on its current monitor.
+#[napi]
+pub fn maximize_window(hwnd: i64) -> Result<()> {
+  show_window(hwnd, SW_MAXIMIZE)
+}
+
+/// Restores `hwnd` from minimized or maximized to its normal size and position.
+#[napi]
+pub fn restore_window(hwnd: i64) -> Result<()> {
+  show_window(hwnd, SW_RESTORE)
+}
+
+pub(crate) fn window_rect(hwnd: HWND) -> Option<RECT> {
+  let mut rect = RECT::default();
+  unsafe { GetWindowRect(hwnd, &mut rect) }.ok()?;
+  Some(rect)
+}
+
+// The visible frame in screen coordinates, without the invisible borders
+fn frame_bounds(hwnd: HWND) -> Option<RECT> {
+  let mut rect = RECT::default();
+  unsafe {
+    DwmGetWindowAttribute(
+      hwnd,
+      DWMWA_EXTENDED_FRAME_BOUNDS,
+      &mut rect as *mut RECT as *mut c_void,
+      size_of::<RECT>() as u32,
+    )
+  }
+  .ok()?;
+  Some(rect)
+}
+
+// How far the window rect extends past the visible frame on each side
+// (left, top, right, bottom). On Windows 10+ this is the invisible resize
+// border/shadow; zero if DWM can't tell us.
+fn frame_insets(hwnd: HWND) -> (i32, i32, i32, i32) {
+  let (Some(window), Some(frame)) = (window_rect(hwnd), frame_bounds(hwnd)) else {
+    return (0, 0, 0, 0);
+  };
+  (
+    frame.left - window.left,
+    frame.top - window.top,
+    window.right - frame.right,
+    window.bottom - frame.bottom,
+  )
+}
+
+// Validates a move and puts the window in its normal state, returning the
+// window rect to apply
+fn prepare_move(
+  hwnd: i64,
+  x: i32,
+  y: i32,
+  width: i32,
+  height: i32,
+  visible_frame: Option<bool>,
+) -> Result<(HWND, RECT)> {
+  if width <= 0 || height <= 0 {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("Invalid window size: {}x{}", width, height),
+    ));
+  }
+  let handle = checked_hwnd(hwnd)?;
+
+  unsafe {
+    if IsIconic(handle).as_bool() || IsZoomed(handle).as_bool() {
+      let _ = ShowWindow(handle, SW_RESTORE);
+    }
+  }
+
+  // Insets are only meaningful once the window is in its normal state
+  let (left, top, right, bottom) = if visible_frame.unwrap_or(false) {
+    frame_insets(handle)
+  } else {
+    (0, 0, 0, 0)
+  };
+  let rect = RECT {
+    left: x - left,
+    top: y - top,
+    right: x + width + right,
+    bottom: y + height + bottom,
+  };
+  Ok((handle, rect))
+}
+
+/// Moves and resizes `hwnd` to the given screen rectangle, restoring it first
+/// if minimized or maximized. With `visibleFrame` the rectangle describes the
+/// visible frame rather than the window rect, so the invisible borders don't
+/// leave gaps when snapping (e.g. a "left half" lines up with the monitor edge).
+#[napi]
+pub fn move_window(
+  hwnd: i64,
+  x: i32,
+  y: i32,
+  width: i32,
+  height: i32,
+  visible_frame: Option<bool>,
+) -> Result<()> {
+  let (handle, rect) = prepare_move(hwnd, x, y, width, height, visible_frame)?;
+  unsafe {
+    SetWindowPos(
+      handle,
+      None,
+      rect.left,
+      rect.top,
+      rect.right - rect.left,
+      rect.bottom - rect.top,
+      SWP_NOZORDER | SWP_NOACTIVATE,
+    )
+  }
+  .map_err(|e| {
+    napi::Error::new(
+      napi::Status::GenericFailure,
+      format!("Failed to move window {}: {}", hwnd, e),
+    )
+  })
+}
+
+/// One entry for `move_windows`, with the same meaning as `move_window`'s arguments
+#[napi(object)]
+pub struct WindowMove {
+  pub hwnd: i64,
+  pub x: i32,
+  pub y: i32,
+  pub width: i32,
+  pub height: i32,
+  pub visible_frame: Option<bool>,
+}
+
+/// Applies several `move_window` calls at once, e.g. for a tiling layout.
+/// The moves are batched with DeferWindowPos so the screen repaints once.
+/// Returns one flag per entry: false if that window was invalid or refused
+/// to move (an elevated window, say); the others still move.
+#[napi]
+pub fn move_windows(moves: Vec<WindowMove>) -> Vec<bool> {
+  let prepared: Vec<Option<(HWND, RECT)>> = moves
+    .iter()
+    .map(|m| prepare_move(m.hwnd, m.x, m.y, m.width, m.height, m.visible_frame).ok())
+    .collect();
+  let targets = || prepared.iter().flatten();
+
+  // A failed DeferWindowPos discards the whole batch; so does a failed
+  // EndDeferWindowPos, which then leaves no clue which window refused
+  let deferred = unsafe {
+    BeginDeferWindowPos(targets().count() as i32).and_then(|batch| {
+      targets()
+        .try_fold(batch, |batch, (handle, rect)| {
+          DeferWindowPos(
+            batch,
+            *handle,
+            None,
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            SWP_NOZORDER | SWP_NOACTIVATE,
+          )
+        })
+        .and_then(|batch| EndDeferWindowPos(batch))
+    })
+  };
+  if deferred.is_ok() {
+    return prepared.iter().map(Option::is_some).collect();
+  }
+
+  // Fall back to one SetWindowPos per window to find out which failed
+  prepared
+    .iter()
+    .map(|target| {
+      target.is_some_and(|(handle, rect)| {
+        unsafe {
+          SetWindowPos(
+            handle,
+            None,
+            rect.left,
+            rect.top,
+            rect.right - rect.left,
+            rect.bottom - rect.top,
+            SWP_NOZORDER | SWP_NOACTIVATE,
+          )
+        }
+        .is_ok()
+      })
+    })
+    .collect()
+}
+
+/// A window's bounds in screen coordinates
+#[napi(object)]
+pub struct WindowRect {
+  pub x: i32,
+  pub y: i32,
+  pub width: i32,
+  pub height: i32,
+}
+
+impl From<RECT> for WindowRect {
+  fn from(rect: RECT) -> Self {
+    WindowRect {
+      x: rect.left,
+      y: rect.top,
+      width: rect.right - rect.left,
+      height: rect.bottom - rect.top,
+    }
+  }
+}
+
+/// Where `hwnd` is on screen, or `null` if it isn't a window. With
+/// `visibleFrame` the bounds exclude the invisible borders, matching what the
+/// user sees and what `move_window` takes with the same flag.
+#[napi]
+pub fn get_window_rect(hwnd: i64, visible_frame: Option<bool>) -> Option<WindowRect> {
+  let handle = checked_hwnd(hwnd).ok()?;
+  let rect = if visible_frame.unwrap_or(false) {
+    // Older systems without DWM frames report the plain window rect
+    frame_bounds(handle).or_else(|| window_rect(handle))?
+  } else {
+    window_rect(handle)?
+  };
+  Some(rect.into())
+}
+
+// Mandatory integrity RID from which a token counts as elevated
+// (SECURITY_MANDATORY_HIGH_RID)
+const HIGH_INTEGRITY_RID: u32 = 0x3000;
+
+// The integrity RID of `process`'s token, e.g. 0x2000 for a normal app
+fn integrity_level(process: HANDLE) -> Option<u32> {
+  let mut token = HANDLE::default();
+  unsafe { OpenProcessToken(process, TOKEN_QUERY, &mut token) }.ok()?;
+
+  // First call reports the size; u64s keep the label suitably aligned
+  let mut len = 0u32;
+  let _ = unsafe { GetTokenInformation(token, TokenIntegrityLevel, None, 0, &mut len) };
+  let mut buffer = vec![0u64; (len as usize).div_ceil(8)];
+  let queried = unsafe {
+    GetTokenInformation(
+      token,
+      TokenIntegrityLevel,
+      Some(buffer.as_mut_ptr() as *mut c_void),
+      len,
+      &mut len,
+    )
+  };
+  let _ = unsafe { CloseHandle(token) };
+  queried.ok()?;
+
+  unsafe {
+    let label = &*(buffer.as_ptr() as *const TOKEN_MANDATORY_LABEL);
+    let sid = label.Label.Sid;
+    // The integrity level is the SID's last sub-authority
+    let count = *GetSidSubAuthorityCount(sid) as u32;
+    Some(*GetSidSubAuthority(sid, count.checked_sub(1)?))
+  }
+}
+
+/// Whether `hwnd` belongs to an elevated (high-integrity) process, or `null`
+/// if that can't be told (invalid handle, or access denied). An unelevated
+/// panepilot can't focus or hook into elevated windows, so the switcher can
+/// label or grey them out instead of silently failing.
+#[napi]
+pub fn get_window_elevation(hwnd: i64) -> Option<bool> {
+  let handle = checked_hwnd(hwnd).ok()?;
+  Some(window_integrity_level(handle)? >= HIGH_INTEGRITY_RID)
+}
+
+// The integrity RID of the process that owns `hwnd`
+fn window_integrity_level(hwnd: HWND) -> Option<u32> {
+  let mut process_id = 0u32;
+  unsafe { GetWindowThreadProcessId(hwnd, Some(&mut process_id)) };
+  let process =
+    unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, process_id) }.ok()?;
+  let level = integrity_level(process);
+  let _ = unsafe { CloseHandle(process) };
+  level
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn wide(title: &str) -> Vec<u16> {
+    title.encode_utf16().chain([0]).collect()
+  }
+
+  #[test]
+  fn title_keeps_multibyte_and_surrogate_pair_characters() {
+    let title = "Ünïcödé 窓 \u{1F980} — build";
+    let buffer = wide(title);
+    assert_eq!(decode_title(&buffer, buffer.len() as i32 - 1), title);
+  }
+
+  #[test]
+  fn title_drops_a_surrogate_pair_split_by_the_read_count() {
+    let buffer = wide("crab \u{1F980}");
+    // "crab " is 5 units; the crab's high surrogate is the 6th
+    assert_eq!(decode_title(&buffer, 6), "crab ");
+    assert_eq!(decode_title(&buffer, 7), "crab \u{1F980}");
+  }
+
+  #[test]
+  fn title_stops_at_the_terminator_and_buffer_end() {
+    let buffer = wide("abc");
+    assert_eq!(decode_title(&buffer, 10), "abc");
+    assert_eq!(decode_title(&buffer, 0), "");
+    assert_eq!(decode_title(&buffer, -1), "");
+  }
+}